@@ -0,0 +1,458 @@
+//! Asynchronous client
+//!
+//! [`AsyncTelegraph`] mirrors [`Telegraph`][crate::Telegraph], but is backed by
+//! the non-blocking [`reqwest::Client`] and its builders expose `async fn send()`.
+//! Request builders, state types and response types are shared with the blocking client.
+
+use std::sync::Arc;
+#[cfg(feature = "upload")]
+use std::path::Path;
+
+use reqwest::Client;
+#[cfg(feature = "upload")]
+use reqwest::multipart;
+#[cfg(feature = "upload")]
+use tokio::io::AsyncReadExt;
+
+#[cfg(feature = "upload")]
+use crate::types::{UploadResult, Media};
+use crate::requests::{
+    CreateAccount, EditAccountInfo, GetAccountInfo,
+    CreatePage, RevokeAccessToken, EditPage, GetPage,
+    GetPageList, GetViews, NoShortName, NoAccessToken,
+    NoTitle, NoContent, NoPath, Pages,
+    Granularity, ViewsDate, ViewsRange
+};
+use crate::store::AccountStore;
+#[cfg(feature = "upload")]
+use crate::upload::{apply_media, file_name, too_big, UploadFile, MAX_UPLOAD_SIZE};
+#[cfg(feature = "upload")]
+use crate::content::local_media;
+#[cfg(feature = "upload")]
+use crate::requests::Content;
+#[cfg(feature = "upload")]
+use crate::types::Node;
+#[cfg(feature = "upload")]
+use crate::DEFAULT_UPLOAD_URL;
+use crate::TelegraphError;
+use crate::retry::RetryPolicy;
+use crate::MethodName;
+
+
+/// `AsyncTelegraph` for calling method builder with async `send`
+#[derive(Clone, Default)]
+pub struct AsyncTelegraph {
+    client: Arc<Client>,
+    method_name: MethodName,
+    retry_policy: RetryPolicy,
+    account_store: Option<AccountStore>
+}
+
+
+impl AsyncTelegraph {
+    /// Constructs a new `AsyncTelegraph`
+    pub fn new() -> Self {
+        AsyncTelegraph::default()
+    }
+
+    pub(crate) fn with_method_name(
+        method_name: MethodName,
+        retry_policy: RetryPolicy,
+        account_store: Option<AccountStore>
+    ) -> Self {
+        AsyncTelegraph { client: Arc::default(), method_name, retry_policy, account_store }
+    }
+
+    /// Use this method to create a new Telegraph [`Account`][crate::types::Account].
+    /// See [`Telegraph::create_account`][crate::Telegraph::create_account].
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// use telegraph_api_rs::AsyncTelegraph;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let telegraph = AsyncTelegraph::new();
+    /// let account = telegraph.create_account()
+    /// .short_name("Short name")
+    /// .author_name("Author name")
+    /// .send()
+    /// .await
+    /// .unwrap();
+    /// # }
+    /// ```
+    pub fn create_account(&self) -> CreateAccount<NoShortName, Client> {
+        CreateAccount::new(
+            self.client.clone(),
+            self.method_name.create_account.clone(),
+            self.account_store.clone()
+        )
+    }
+
+    /// Use this method to update information about a Telegraph account.
+    /// See [`Telegraph::edit_account_info`][crate::Telegraph::edit_account_info].
+    pub fn edit_account_info(&self) -> EditAccountInfo<NoAccessToken, Client> {
+        EditAccountInfo::new(
+            self.client.clone(),
+            self.method_name.edit_account_info.clone(),
+            self.retry_policy
+        )
+    }
+
+    /// Use this method to get information about a Telegraph account.
+    /// See [`Telegraph::get_account_info`][crate::Telegraph::get_account_info].
+    pub fn get_account_info(&self) -> GetAccountInfo<NoAccessToken, Client> {
+        GetAccountInfo::new(
+            self.client.clone(),
+            self.method_name.get_account_info.clone(),
+            self.retry_policy
+        )
+    }
+
+    /// Use this method to revoke `access_token` and generate a new one.
+    /// See [`Telegraph::revoke_access_token`][crate::Telegraph::revoke_access_token].
+    pub fn revoke_access_token(&self) -> RevokeAccessToken<NoAccessToken, Client> {
+        RevokeAccessToken::new(
+            self.client.clone(),
+            self.method_name.revoke_access_token.clone(),
+            self.account_store.clone()
+        )
+    }
+
+    /// Use this method to create a new Telegraph page.
+    /// See [`Telegraph::create_page`][crate::Telegraph::create_page].
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use telegraph_api_rs::{AsyncTelegraph, build_content, types::Account};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let telegraph = AsyncTelegraph::new();
+    /// # let account = Account::default();
+    /// # let token = account.access_token.as_ref().unwrap();
+    /// let content = r#"[{"tag": "p", "children": ["Hello world"]}]"#;
+    /// let page = telegraph.create_page()
+    /// .access_token(token)
+    /// .title("Hello world")
+    /// .content(build_content(content).unwrap())
+    /// .send()
+    /// .await
+    /// .unwrap();
+    /// # }
+    /// ```
+    pub fn create_page(&self) -> CreatePage<NoAccessToken, NoTitle, NoContent, Client> {
+        CreatePage::new(
+            self.client.clone(),
+            self.method_name.create_page.clone(),
+            self.retry_policy
+        )
+    }
+
+    /// Use this method to edit an existing Telegraph page.
+    /// See [`Telegraph::edit_page`][crate::Telegraph::edit_page].
+    pub fn edit_page(&self) -> EditPage<NoAccessToken, NoPath, NoTitle, NoContent, Client> {
+        EditPage::new(
+            self.client.clone(),
+            self.method_name.edit_page.clone(),
+            self.retry_policy
+        )
+    }
+
+    /// Use this method to get a Telegraph page.
+    /// See [`Telegraph::get_page`][crate::Telegraph::get_page].
+    pub fn get_page(&self) -> GetPage<NoPath, Client> {
+        GetPage::new(
+            self.client.clone(),
+            self.method_name.get_page.clone(),
+            self.retry_policy
+        )
+    }
+
+    /// Use this method to get a list of pages belonging to a Telegraph account.
+    /// See [`Telegraph::get_page_list`][crate::Telegraph::get_page_list].
+    pub fn get_page_list(&self) -> GetPageList<NoAccessToken, Client> {
+        GetPageList::new(
+            self.client.clone(),
+            self.method_name.get_page_list.clone(),
+            self.retry_policy
+        )
+    }
+
+    /// Use this method to get the number of views for a Telegraph article.
+    /// See [`Telegraph::get_views`][crate::Telegraph::get_views].
+    pub fn get_views(&self) -> GetViews<NoPath, Client> {
+        GetViews::new(
+            self.client.clone(),
+            self.method_name.get_views.clone(),
+            self.retry_policy
+        )
+    }
+
+    /// Pages of the account, see [`Telegraph::pages`][crate::Telegraph::pages].
+    /// Use [`Pages::into_stream`] to get a `Stream`.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// use futures_util::StreamExt;
+    /// # use telegraph_api_rs::{AsyncTelegraph, types::Account};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// # let telegraph = AsyncTelegraph::new();
+    /// # let account = Account::default();
+    /// let mut pages = Box::pin(telegraph.pages(&account.access_token.unwrap()).into_stream());
+    /// while let Some(page) = pages.next().await {
+    ///     println!("{}", page.unwrap().url);
+    /// }
+    /// # }
+    /// ```
+    pub fn pages(&self, access_token: &str) -> Pages<Client> {
+        Pages::new(
+            self.client.clone(),
+            self.method_name.get_page_list.clone(),
+            self.retry_policy,
+            access_token
+        )
+    }
+
+    /// Number of page views over a date range.
+    /// See [`Telegraph::views_range`][crate::Telegraph::views_range].
+    pub async fn views_range(
+        &self,
+        path: &str,
+        from: ViewsDate,
+        to: ViewsDate,
+        granularity: Granularity
+    ) -> Result<Vec<(ViewsDate, u32)>, TelegraphError> {
+        ViewsRange::new(
+            self.client.clone(),
+            self.method_name.get_views.clone(),
+            self.retry_policy,
+            path, from, to, granularity
+        )?.send().await
+    }
+
+    #[cfg(feature = "upload")]
+    async fn _upload(client: &Client, url: &str, files: Vec<UploadFile>) -> Result<Vec<Media>, TelegraphError> {
+        let mut form = multipart::Form::new();
+        for (index, file) in files.into_iter().enumerate() {
+            form = form.part(index.to_string(), file.into_async_part()?);
+        }
+
+        let response = client.post(url)
+        .multipart(form)
+        .send()
+        .await?;
+
+        match response.json::<UploadResult>().await? {
+            UploadResult::Error { error } => Err(TelegraphError::ApiError(error.into())),
+            UploadResult::Ok(vec) => Ok(vec)
+        }
+    }
+
+    #[cfg(feature = "upload")]
+    async fn read_files<T>(files: &[T]) -> Result<Vec<UploadFile>, TelegraphError>
+    where T: AsRef<Path>
+    {
+        let mut upload_files = Vec::with_capacity(files.len());
+        for path in files {
+            let name = file_name(path.as_ref());
+            let file = tokio::fs::File::open(path).await?;
+            let size = file.metadata().await?.len();
+            if size > MAX_UPLOAD_SIZE as u64 {
+                return Err(too_big(&name, usize::try_from(size).unwrap_or(usize::MAX)));
+            }
+            let mut bytes = vec![];
+            file.take(MAX_UPLOAD_SIZE as u64 + 1).read_to_end(&mut bytes).await?;
+            if bytes.len() > MAX_UPLOAD_SIZE {
+                return Err(too_big(&name, bytes.len()));
+            }
+            upload_files.push(UploadFile::from_bytes(bytes, &name));
+        }
+        Ok(upload_files)
+    }
+
+    #[cfg(feature = "upload")]
+    /// Upload files from memory to telegraph.
+    /// See [`Telegraph::upload_files`][crate::Telegraph::upload_files].
+    pub async fn upload_files(&self, files: Vec<UploadFile>) -> Result<Vec<Media>, TelegraphError> {
+        Self::_upload(&self.client, &self.method_name.upload, files).await
+    }
+
+    #[cfg(feature = "upload")]
+    /// Upload files from memory to telegraph with custom client
+    pub async fn upload_files_with(client: &Client, files: Vec<UploadFile>) -> Result<Vec<Media>, TelegraphError> {
+        Self::_upload(client, DEFAULT_UPLOAD_URL, files).await
+    }
+
+    #[cfg(feature = "upload")]
+    /// Uploads local media of the content and rewrites their `src`.
+    /// See [`Telegraph::upload_local_media`][crate::Telegraph::upload_local_media].
+    pub async fn upload_local_media<P>(&self, content: &mut [Node], base_dir: P) -> Result<usize, TelegraphError>
+    where P: AsRef<Path>
+    {
+        let sources = local_media(content);
+        if sources.is_empty() {
+            return Ok(0);
+        }
+        let paths: Vec<_> = sources.iter().map(|src| base_dir.as_ref().join(src)).collect();
+        let media = self.upload(&paths).await?;
+        apply_media(content, &sources, &media);
+        Ok(sources.len())
+    }
+
+    #[cfg(feature = "upload")]
+    /// [`create_page`][AsyncTelegraph::create_page] with the content after uploading its local media.
+    pub async fn create_page_with_media<P>(&self, mut content: Vec<Node>, base_dir: P)
+        -> Result<CreatePage<NoAccessToken, NoTitle, Content, Client>, TelegraphError>
+    where P: AsRef<Path>
+    {
+        self.upload_local_media(&mut content, base_dir).await?;
+        Ok(self.create_page().content(content))
+    }
+
+    #[cfg(feature = "upload")]
+    /// [`edit_page`][AsyncTelegraph::edit_page] with the content after uploading its local media.
+    pub async fn edit_page_with_media<P>(&self, mut content: Vec<Node>, base_dir: P)
+        -> Result<EditPage<NoAccessToken, NoPath, NoTitle, Content, Client>, TelegraphError>
+    where P: AsRef<Path>
+    {
+        self.upload_local_media(&mut content, base_dir).await?;
+        Ok(self.edit_page().content(content))
+    }
+
+    #[cfg(feature = "upload")]
+    /// Upload files to telegraph
+    ///
+    /// # Example
+    /// ``` rust, no_run
+    /// # use telegraph_api_rs::AsyncTelegraph;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let telegraph = AsyncTelegraph::new();
+    /// let files = vec!["1.jpg", "2.png"];
+    /// let media = telegraph.upload(&files).await;
+    /// # }
+    /// ```
+    pub async fn upload<T>(&self, files: &[T]) -> Result<Vec<Media>, TelegraphError>
+    where T: AsRef<Path>
+    {
+        self.upload_files(Self::read_files(files).await?).await
+    }
+
+    #[cfg(feature = "upload")]
+    /// Upload files to telegraph with custom client
+    pub async fn upload_with<T>(client: &Client, files: &[T]) -> Result<Vec<Media>, TelegraphError>
+    where T: AsRef<Path>
+    {
+        Self::upload_files_with(client, Self::read_files(files).await?).await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use futures_util::TryStreamExt;
+
+    use crate::build_content;
+    use crate::error::ApiErrorKind;
+    use crate::requests::{Granularity, ViewsDate};
+    use crate::testing::FakeTelegraph;
+    use crate::types::Node;
+    use crate::TelegraphError;
+
+    fn content() -> Vec<Node> {
+        build_content(r#"[{"tag": "p", "children": ["Hello world"]}]"#).unwrap()
+    }
+
+    #[tokio::test]
+    async fn account_methods() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.async_telegraph();
+        let account = telegraph.create_account()
+            .short_name("Async")
+            .author_name("Author")
+            .send()
+            .await
+            .unwrap();
+        let token = account.access_token.unwrap();
+
+        let edited = telegraph.edit_account_info()
+            .access_token(&token)
+            .author_name("New author")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(edited.author_name.unwrap(), "New author");
+        let info = telegraph.get_account_info().access_token(&token).send().await.unwrap();
+        assert_eq!(info.short_name.unwrap(), "Async");
+
+        let revoked = telegraph.revoke_access_token().access_token(&token).send().await.unwrap();
+        assert_ne!(revoked.access_token.as_ref().unwrap(), &token);
+        let error = telegraph.get_account_info().access_token(&token).send().await.unwrap_err();
+        assert!(matches!(error, TelegraphError::ApiError(ApiErrorKind::AccessTokenInvalid)));
+    }
+
+    #[tokio::test]
+    async fn page_methods() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.async_telegraph();
+        let token = telegraph.create_account().short_name("Async").send().await.unwrap().access_token.unwrap();
+
+        // Futures are Send, so requests run on other tasks of the runtime
+        let tasks: Vec<_> = (0..3).map(|index| {
+            let request = telegraph.create_page()
+                .access_token(&token)
+                .title(&format!("Page {}", index))
+                .content(content());
+            tokio::spawn(request.send())
+        }).collect();
+        let mut paths = vec![];
+        for task in tasks {
+            paths.push(task.await.unwrap().unwrap().path);
+        }
+
+        let edited = telegraph.edit_page()
+            .access_token(&token)
+            .path(&paths[0])
+            .title("Edited")
+            .content(content())
+            .return_content(true)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!((edited.title.as_str(), edited.content.unwrap().len()), ("Edited", 1));
+        assert_eq!(telegraph.get_page().path(&paths[0]).send().await.unwrap().title, "Edited");
+
+        let page_list = telegraph.get_page_list().access_token(&token).send().await.unwrap();
+        assert_eq!(page_list.total_count, 3);
+        let pages: Vec<_> = telegraph.pages(&token).into_stream().try_collect().await.unwrap();
+        assert_eq!(pages.len(), 3);
+
+        server.add_views(&paths[1], 2024, 5, 1, 10, 4);
+        let views = telegraph.get_views().path(&paths[1]).year(2024).send().await.unwrap();
+        assert_eq!(views.views, 4);
+        let daily = telegraph.views_range(&paths[1], ViewsDate::new(2024, 5, 1), ViewsDate::new(2024, 5, 2), Granularity::Day)
+            .await
+            .unwrap();
+        assert_eq!(daily, vec![(ViewsDate::new(2024, 5, 1), 4), (ViewsDate::new(2024, 5, 2), 0)]);
+
+        let error = telegraph.get_page().path("Missing-01-01").send().await.unwrap_err();
+        assert!(matches!(error, TelegraphError::ApiError(ApiErrorKind::PageNotFound)));
+    }
+
+    #[cfg(feature = "upload")]
+    #[tokio::test]
+    async fn upload() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.async_telegraph();
+        let file = std::env::temp_dir().join(format!("telegraph-async-upload-{}.png", std::process::id()));
+        std::fs::write(&file, b"\x89PNG\r\n\x1a\n").unwrap();
+
+        let media = telegraph.upload(&[&file]).await.unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(media.len(), 1);
+        assert!(media[0].src.starts_with("/file/"));
+        assert_eq!(server.uploads(), 1);
+    }
+}
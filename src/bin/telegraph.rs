@@ -0,0 +1,554 @@
+//! `telegraph` command-line tool
+//!
+//! Every API method as a subcommand, built on the library builders.
+//! The access token is taken from `--token`, `TELEGRAPH_TOKEN`,
+//! or from the account store at `--config` (see [`AccountStore`]),
+//! which also records the accounts created and the tokens revoked by the tool.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use telegraph_api_rs::content::{from_html, from_markdown};
+use telegraph_api_rs::types::{Account, AccountField, Node, Page};
+use telegraph_api_rs::{build_content, AccountStore, Telegraph, TelegraphError, DEFAULT_API_BASE, DEFAULT_UPLOAD_URL};
+
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+
+/// Command-line client of the Telegraph API
+#[derive(Parser)]
+#[command(name = "telegraph", version)]
+struct Cli {
+    #[command(flatten)]
+    global: Global,
+
+    #[command(subcommand)]
+    command: Command
+}
+
+
+#[derive(Args)]
+struct Global {
+    /// Access token of the account
+    #[arg(long, global = true, env = "TELEGRAPH_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    /// Short name of the stored account to use
+    #[arg(long, global = true, env = "TELEGRAPH_ACCOUNT")]
+    account: Option<String>,
+
+    /// Account store file, `~/.config/telegraph/accounts.json` by default
+    #[arg(long, global = true, env = "TELEGRAPH_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Base url of the API methods
+    #[arg(long, global = true, env = "TELEGRAPH_API_BASE", default_value = DEFAULT_API_BASE)]
+    api_base: String,
+
+    /// Url of the upload endpoint
+    #[arg(long, global = true, env = "TELEGRAPH_UPLOAD_URL", default_value = DEFAULT_UPLOAD_URL)]
+    upload_url: String,
+
+    /// Print results as JSON instead of tables
+    #[arg(long, global = true)]
+    json: bool
+}
+
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage accounts
+    #[command(subcommand)]
+    Account(AccountCommand),
+    /// Manage pages
+    #[command(subcommand)]
+    Page(PageCommand),
+    /// Upload images and videos
+    Upload {
+        /// Files to upload
+        #[arg(required = true)]
+        files: Vec<PathBuf>
+    },
+    /// Mirror the Markdown files of a directory to pages
+    Sync {
+        /// Directory with Markdown files
+        dir: PathBuf,
+        /// Manifest file, `.telegraph-sync.json` in the directory by default
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+        #[command(flatten)]
+        author: Author
+    },
+    /// Write all pages of the account to a directory
+    Export {
+        /// Export directory
+        dir: PathBuf,
+        /// Also render the pages to HTML
+        #[arg(long)]
+        html: bool,
+        /// Also render the pages to Markdown
+        #[arg(long)]
+        markdown: bool
+    }
+}
+
+
+#[derive(Subcommand)]
+enum AccountCommand {
+    /// Create an account and save it to the store
+    Create {
+        /// Account name
+        short_name: String,
+        #[command(flatten)]
+        author: Author
+    },
+    /// Edit the account
+    Edit {
+        /// New account name
+        #[arg(long)]
+        short_name: Option<String>,
+        #[command(flatten)]
+        author: Author
+    },
+    /// Show the account
+    Info,
+    /// Revoke the access token and save the new one to the store
+    Revoke
+}
+
+
+#[derive(Subcommand)]
+enum PageCommand {
+    /// Create a page from a JSON, HTML or Markdown file
+    Create {
+        /// Page title
+        #[arg(long)]
+        title: String,
+        #[command(flatten)]
+        source: Source,
+        #[command(flatten)]
+        author: Author
+    },
+    /// Replace the content of a page
+    Edit {
+        /// Page path
+        path: String,
+        /// Page title
+        #[arg(long)]
+        title: String,
+        #[command(flatten)]
+        source: Source,
+        #[command(flatten)]
+        author: Author
+    },
+    /// Show a page
+    Get {
+        /// Page path
+        path: String,
+        /// Include the content
+        #[arg(long)]
+        content: bool
+    },
+    /// List the pages of the account
+    List {
+        /// Number of pages to skip
+        #[arg(long, default_value_t = 0)]
+        offset: i32,
+        /// Number of pages to return, 0-200
+        #[arg(long, default_value_t = 50)]
+        limit: i32
+    },
+    /// Show the number of views of a page
+    Views {
+        /// Page path
+        path: String,
+        /// Year, required with `--month`
+        #[arg(long)]
+        year: Option<i32>,
+        /// Month, required with `--day`
+        #[arg(long, requires = "year")]
+        month: Option<i32>,
+        /// Day of the month, required with `--hour`
+        #[arg(long, requires = "month")]
+        day: Option<i32>,
+        /// Hour
+        #[arg(long, requires = "day")]
+        hour: Option<i32>
+    }
+}
+
+
+#[derive(Args)]
+struct Author {
+    /// Author name
+    #[arg(long)]
+    author_name: Option<String>,
+    /// Author profile link
+    #[arg(long)]
+    author_url: Option<String>
+}
+
+
+#[derive(Args)]
+struct Source {
+    /// Content file
+    file: PathBuf,
+    /// Format of the content file, detected from the extension by default
+    #[arg(long, value_enum)]
+    format: Option<Format>
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Json,
+    Html,
+    Markdown
+}
+
+
+impl Format {
+    fn detect(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "html" | "htm" => Some(Format::Html),
+            "md" | "markdown" => Some(Format::Markdown),
+            _ => None
+        }
+    }
+}
+
+
+impl Source {
+    fn read(&self) -> CliResult<Vec<Node>> {
+        let format = self.format
+            .or_else(|| Format::detect(&self.file))
+            .ok_or("unknown content format, pass --format")?;
+        let text = fs::read_to_string(&self.file)?;
+        Ok(parse_content(&text, format)?)
+    }
+}
+
+
+fn parse_content(text: &str, format: Format) -> Result<Vec<Node>, TelegraphError> {
+    match format {
+        Format::Json => build_content(text),
+        Format::Html => from_html(text),
+        Format::Markdown => from_markdown(text)
+    }
+}
+
+
+struct Context {
+    global: Global,
+    store: AccountStore,
+    telegraph: Telegraph
+}
+
+
+impl Context {
+    fn new(global: Global) -> CliResult<Self> {
+        let store = AccountStore::open(global.config.clone().unwrap_or_else(default_config))?;
+        let telegraph = Telegraph::builder()
+            .api_base(&global.api_base)
+            .upload_url(&global.upload_url)
+            .account_store(store.clone())
+            .build();
+        Ok(Context { global, store, telegraph })
+    }
+
+    /// Creates the directory of the store before a command saves an account to it.
+    fn create_store_dir(&self) -> CliResult<()> {
+        if let Some(parent) = self.store.path().parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(())
+    }
+
+    /// Token from `--token`, the account named by `--account`,
+    /// or the only account of the store.
+    fn access_token(&self) -> CliResult<String> {
+        if let Some(token) = &self.global.token {
+            return Ok(token.clone());
+        }
+        if let Some(short_name) = &self.global.account {
+            return self.store.get(short_name)
+                .map(|account| account.access_token)
+                .ok_or_else(|| format!("account {} is not in {}", short_name, self.store.path().display()).into());
+        }
+        match self.store.accounts().as_slice() {
+            [account] => Ok(account.access_token.clone()),
+            [] => Err("no access token, pass --token or create an account".into()),
+            _ => Err("several stored accounts, pass --account".into())
+        }
+    }
+
+    fn print<T: Serialize>(&self, value: &T, rows: Vec<Vec<String>>, headers: &[&str]) -> CliResult<()> {
+        if self.global.json {
+            println!("{}", serde_json::to_string_pretty(value)?);
+        } else {
+            print!("{}", table(headers, &rows));
+        }
+        Ok(())
+    }
+
+    fn print_account(&self, account: &Account) -> CliResult<()> {
+        let rows = fields(&[
+            ("short_name", account.short_name.clone()),
+            ("author_name", account.author_name.clone()),
+            ("author_url", account.author_url.clone()),
+            ("access_token", account.access_token.clone()),
+            ("auth_url", account.auth_url.clone()),
+            ("page_count", account.page_count.map(|count| count.to_string()))
+        ]);
+        self.print(account, rows, &[])
+    }
+
+    /// Prints the account also when saving it to the store failed,
+    /// its access token is not returned again.
+    fn print_saved_account(&self, result: Result<Account, TelegraphError>) -> CliResult<()> {
+        match result {
+            Err(TelegraphError::AccountNotSaved(account, error)) => {
+                self.print_account(&account)?;
+                Err(format!("account not saved to {}: {}", self.store.path().display(), error).into())
+            },
+            result => self.print_account(&result?)
+        }
+    }
+
+    fn print_page(&self, page: &Page) -> CliResult<()> {
+        let rows = fields(&[
+            ("path", Some(page.path.clone())),
+            ("url", Some(page.url.clone())),
+            ("title", Some(page.title.clone())),
+            ("description", Some(page.description.clone())),
+            ("author_name", page.author_name.clone()),
+            ("author_url", page.author_url.clone()),
+            ("views", Some(page.views.to_string())),
+            ("can_edit", page.can_edit.map(|can_edit| can_edit.to_string()))
+        ]);
+        self.print(page, rows, &[])
+    }
+}
+
+
+fn default_config() -> PathBuf {
+    std::env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".config/telegraph/accounts.json"))
+        .unwrap_or_else(|| PathBuf::from("telegraph-accounts.json"))
+}
+
+
+/// Rows of the fields with a non-empty value.
+fn fields(fields: &[(&str, Option<String>)]) -> Vec<Vec<String>> {
+    fields.iter()
+        .filter_map(|(name, value)| Some(vec![name.to_string(), value.clone().filter(|value| !value.is_empty())?]))
+        .collect()
+}
+
+
+/// Left-aligned columns separated by two spaces, with an optional header row.
+fn table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut lines: Vec<Vec<String>> = vec![];
+    if !headers.is_empty() {
+        lines.push(headers.iter().map(|header| header.to_uppercase()).collect());
+    }
+    lines.extend(rows.iter().cloned());
+    let columns = lines.iter().map(Vec::len).max().unwrap_or_default();
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| lines.iter().filter_map(|line| line.get(column)).map(|cell| cell.chars().count()).max().unwrap_or_default())
+        .collect();
+    let mut output = String::new();
+    for line in lines {
+        let cells: Vec<String> = line.iter()
+            .enumerate()
+            .map(|(column, cell)| format!("{:width$}", cell, width = widths[column]))
+            .collect();
+        output.push_str(cells.join("  ").trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+
+fn run(cli: Cli) -> CliResult<()> {
+    let context = Context::new(cli.global)?;
+    let telegraph = &context.telegraph;
+    match cli.command {
+        Command::Account(AccountCommand::Create { short_name, author }) => {
+            let mut request = telegraph.create_account().short_name(&short_name);
+            if let Some(author_name) = &author.author_name {
+                request = request.author_name(author_name);
+            }
+            if let Some(author_url) = &author.author_url {
+                request = request.author_url(author_url);
+            }
+            context.create_store_dir()?;
+            context.print_saved_account(request.send())
+        },
+        Command::Account(AccountCommand::Edit { short_name, author }) => {
+            let mut request = telegraph.edit_account_info().access_token(&context.access_token()?);
+            if let Some(short_name) = &short_name {
+                request = request.short_name(short_name);
+            }
+            if let Some(author_name) = &author.author_name {
+                request = request.author_name(author_name);
+            }
+            if let Some(author_url) = &author.author_url {
+                request = request.author_url(author_url);
+            }
+            context.print_account(&request.send()?)
+        },
+        Command::Account(AccountCommand::Info) => {
+            let account = telegraph.get_account_info()
+                .access_token(&context.access_token()?)
+                .fields(vec![
+                    AccountField::ShortName,
+                    AccountField::AuthorName,
+                    AccountField::AuthorUrl,
+                    AccountField::AuthUrl,
+                    AccountField::PageCount
+                ])
+                .send()?;
+            context.print_account(&account)
+        },
+        Command::Account(AccountCommand::Revoke) => {
+            let request = telegraph.revoke_access_token().access_token(&context.access_token()?);
+            context.create_store_dir()?;
+            let result = request.send();
+            context.print_saved_account(result)
+        },
+        Command::Page(PageCommand::Create { title, source, author }) => {
+            let mut request = telegraph.create_page()
+                .access_token(&context.access_token()?)
+                .title(&title)
+                .content(source.read()?);
+            if let Some(author_name) = &author.author_name {
+                request = request.author_name(author_name);
+            }
+            if let Some(author_url) = &author.author_url {
+                request = request.author_url(author_url);
+            }
+            context.print_page(&request.send()?)
+        },
+        Command::Page(PageCommand::Edit { path, title, source, author }) => {
+            let mut request = telegraph.edit_page()
+                .access_token(&context.access_token()?)
+                .path(&path)
+                .title(&title)
+                .content(source.read()?);
+            if let Some(author_name) = &author.author_name {
+                request = request.author_name(author_name);
+            }
+            if let Some(author_url) = &author.author_url {
+                request = request.author_url(author_url);
+            }
+            context.print_page(&request.send()?)
+        },
+        Command::Page(PageCommand::Get { path, content }) => {
+            let page = telegraph.get_page().path(&path).return_content(content).send()?;
+            context.print_page(&page)
+        },
+        Command::Page(PageCommand::List { offset, limit }) => {
+            let page_list = telegraph.get_page_list()
+                .access_token(&context.access_token()?)
+                .offset(offset)
+                .limit(limit)
+                .send()?;
+            let rows = page_list.pages.iter()
+                .map(|page| vec![page.path.clone(), page.title.clone(), page.views.to_string()])
+                .collect();
+            context.print(&page_list, rows, &["path", "title", "views"])
+        },
+        Command::Page(PageCommand::Views { path, year, month, day, hour }) => {
+            let mut request = telegraph.get_views().path(&path);
+            if let Some(year) = year {
+                request = request.year(year);
+            }
+            if let Some(month) = month {
+                request = request.month(month);
+            }
+            if let Some(day) = day {
+                request = request.day(day);
+            }
+            if let Some(hour) = hour {
+                request = request.hour(hour);
+            }
+            let views = request.send()?;
+            context.print(&views, vec![vec![path, views.views.to_string()]], &["path", "views"])
+        },
+        Command::Upload { files } => {
+            let media = telegraph.upload(&files)?;
+            let rows = files.iter()
+                .zip(&media)
+                .map(|(file, media)| vec![file.display().to_string(), media.src.clone()])
+                .collect();
+            context.print(&media, rows, &["file", "src"])
+        },
+        Command::Sync { dir, manifest, author } => {
+            let mut sync = telegraph.sync_dir(&context.access_token()?, dir);
+            if let Some(manifest) = &manifest {
+                sync = sync.manifest(manifest);
+            }
+            if let Some(author_name) = &author.author_name {
+                sync = sync.author_name(author_name);
+            }
+            if let Some(author_url) = &author.author_url {
+                sync = sync.author_url(author_url);
+            }
+            let report = sync.run()?;
+            let rows = report.files.iter()
+                .map(|file| vec![file.file.clone(), file.path.clone(), file.action.to_string()])
+                .collect();
+            context.print(&report.files, rows, &["file", "path", "action"])
+        },
+        Command::Export { dir, html, markdown } => {
+            let index = telegraph.export(&context.access_token()?, dir).html(html).markdown(markdown).run()?;
+            let rows = index.pages.iter()
+                .map(|page| vec![page.path.clone(), page.title.clone(), page.views.to_string()])
+                .collect();
+            context.print(&index, rows, &["path", "title", "views"])
+        }
+    }
+}
+
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use clap::CommandFactory;
+
+    use super::{parse_content, table, Cli, Format};
+
+    #[test]
+    fn cli_arguments() {
+        Cli::command().debug_assert();
+        assert_eq!(Format::detect(Path::new("post.MD")), Some(Format::Markdown));
+        assert_eq!(Format::detect(Path::new("post.htm")), Some(Format::Html));
+        assert_eq!(Format::detect(Path::new("post.txt")), None);
+
+        let json = parse_content(r#"[{"tag": "p", "children": ["Hello"]}]"#, Format::Json).unwrap();
+        assert_eq!(parse_content("<p>Hello</p>", Format::Html).unwrap(), json);
+        assert_eq!(parse_content("Hello", Format::Markdown).unwrap(), json);
+
+        let rows = vec![vec!["a".to_string(), "Title".to_string()], vec!["long-path".to_string(), "T".to_string()]];
+        assert_eq!(table(&["path", "title"], &rows), "PATH       TITLE\na          Title\nlong-path  T\n");
+    }
+}
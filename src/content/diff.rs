@@ -0,0 +1,355 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::slice;
+
+use crate::content::to_html;
+use crate::types::{Node, NodeElement, NodeTag};
+
+
+/// Change between two content trees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Indices from the top-level node down to the changed node,
+    /// in the old tree for [`ChangeKind::Removed`] and in the new tree otherwise
+    pub path: Vec<usize>,
+    /// Kind of the change
+    pub kind: ChangeKind
+}
+
+
+/// Kind of [`Change`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Node is only in the new tree
+    Inserted(Node),
+    /// Node is only in the old tree
+    Removed(Node),
+    /// Unchanged node moved from another position
+    Moved {
+        /// Path of the node in the old tree
+        from: Vec<usize>,
+        /// The node
+        node: Node
+    },
+    /// Text node changed
+    TextChanged {
+        /// Old text
+        old: String,
+        /// New text
+        new: String
+    },
+    /// Element tag changed
+    TagChanged {
+        /// Old tag
+        old: Option<NodeTag>,
+        /// New tag
+        new: Option<NodeTag>
+    },
+    /// Element attribute added, removed or changed
+    AttrChanged {
+        /// Name of the attribute
+        name: String,
+        /// Old value
+        old: Option<String>,
+        /// New value
+        new: Option<String>
+    }
+}
+
+
+/// Changes from an old content tree to a new one, see [`diff`]
+///
+/// [`Display`][fmt::Display] renders the changes in a unified format:
+/// a `@@ path @@` header per change followed by the removed (`-`) and the added (`+`) lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentDiff {
+    /// Changes in document order
+    pub changes: Vec<Change>
+}
+
+
+impl ContentDiff {
+    /// `true` if the trees are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+
+fn html(node: &Node) -> String {
+    to_html(slice::from_ref(node))
+}
+
+
+fn tag_name(tag: &Option<NodeTag>) -> &'static str {
+    tag.as_ref().map_or("", NodeTag::as_str)
+}
+
+
+impl fmt::Display for ContentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "@@ {:?} @@", change.path)?;
+            match &change.kind {
+                ChangeKind::Inserted(node) => writeln!(f, "+ {}", html(node))?,
+                ChangeKind::Removed(node) => writeln!(f, "- {}", html(node))?,
+                ChangeKind::Moved { from, node } => writeln!(f, "> {} (moved from {:?})", html(node), from)?,
+                ChangeKind::TextChanged { old, new } => writeln!(f, "- {:?}\n+ {:?}", old, new)?,
+                ChangeKind::TagChanged { old, new } => writeln!(f, "- <{}>\n+ <{}>", tag_name(old), tag_name(new))?,
+                ChangeKind::AttrChanged { name, old, new } => {
+                    if let Some(old) = old {
+                        writeln!(f, "- {}={:?}", name, old)?;
+                    }
+                    if let Some(new) = new {
+                        writeln!(f, "+ {}={:?}", name, new)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/// Largest LCS table, in cells, built by [`common_nodes`]
+const MAX_LCS_CELLS: usize = 1 << 20;
+
+
+/// Pairs of indices of the longest common subsequence of equal nodes.
+///
+/// The common prefix and suffix are matched directly, if the rest is
+/// too big for the LCS table its equal nodes are paired by position.
+fn common_nodes(old: &[Node], new: &[Node]) -> Vec<(usize, usize)> {
+    let prefix = old.iter().zip(new).take_while(|(old, new)| old == new).count();
+    let suffix = old[prefix..].iter().rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let (middle_old, middle_new) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|index| (index, index)).collect();
+    if (middle_old.len() + 1).saturating_mul(middle_new.len() + 1) > MAX_LCS_CELLS {
+        pairs.extend(middle_old.iter().zip(middle_new)
+            .enumerate()
+            .filter(|(_, (old, new))| old == new)
+            .map(|(index, _)| (prefix + index, prefix + index)));
+    } else {
+        pairs.extend(lcs(middle_old, middle_new).into_iter().map(|(i, j)| (prefix + i, prefix + j)));
+    }
+    pairs.extend((0..suffix).map(|index| (old.len() - suffix + index, new.len() - suffix + index)));
+    pairs
+}
+
+
+fn lcs(old: &[Node], new: &[Node]) -> Vec<(usize, usize)> {
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut pairs = vec![];
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+
+fn child(path: &[usize], index: usize) -> Vec<usize> {
+    let mut path = path.to_vec();
+    path.push(index);
+    path
+}
+
+
+fn diff_children(old: &[Node], new: &[Node], old_path: &[usize], new_path: &[usize], changes: &mut Vec<Change>) {
+    let common = common_nodes(old, new);
+    let mut old_matched = vec![false; old.len()];
+    let mut new_matched = vec![false; new.len()];
+    for &(i, j) in &common {
+        old_matched[i] = true;
+        new_matched[j] = true;
+    }
+
+    let mut moved_from = vec![None; new.len()];
+    for j in 0..new.len() {
+        if new_matched[j] {
+            continue;
+        }
+        if let Some(i) = (0..old.len()).find(|&i| !old_matched[i] && old[i] == new[j]) {
+            old_matched[i] = true;
+            new_matched[j] = true;
+            moved_from[j] = Some(i);
+        }
+    }
+
+    // Unmatched nodes between two common nodes are compared in order,
+    // the rest of the longer side is removed or inserted.
+    let (mut old_start, mut new_start) = (0, 0);
+    for (old_end, new_end) in common.into_iter().chain([(old.len(), new.len())]) {
+        let mut old_gap = (old_start..old_end).filter(|&i| !old_matched[i]);
+        for j in new_start..new_end {
+            let kind = match moved_from[j] {
+                Some(i) => ChangeKind::Moved { from: child(old_path, i), node: new[j].clone() },
+                None => match old_gap.next() {
+                    Some(i) => {
+                        diff_node(&old[i], &new[j], &child(old_path, i), &child(new_path, j), changes);
+                        continue;
+                    },
+                    None => ChangeKind::Inserted(new[j].clone())
+                }
+            };
+            changes.push(Change { path: child(new_path, j), kind });
+        }
+        for i in old_gap {
+            changes.push(Change { path: child(old_path, i), kind: ChangeKind::Removed(old[i].clone()) });
+        }
+        old_start = old_end + 1;
+        new_start = new_end + 1;
+    }
+}
+
+
+fn diff_node(old: &Node, new: &Node, old_path: &[usize], new_path: &[usize], changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Node::String(old), Node::String(new)) => {
+            if old != new {
+                let kind = ChangeKind::TextChanged { old: old.clone(), new: new.clone() };
+                changes.push(Change { path: new_path.to_vec(), kind });
+            }
+        },
+        (Node::NodeElement(old), Node::NodeElement(new)) => diff_element(old, new, old_path, new_path, changes),
+        _ => {
+            changes.push(Change { path: old_path.to_vec(), kind: ChangeKind::Removed(old.clone()) });
+            changes.push(Change { path: new_path.to_vec(), kind: ChangeKind::Inserted(new.clone()) });
+        }
+    }
+}
+
+
+fn diff_element(old: &NodeElement, new: &NodeElement, old_path: &[usize], new_path: &[usize], changes: &mut Vec<Change>) {
+    if old.tag != new.tag {
+        changes.push(Change { path: new_path.to_vec(), kind: ChangeKind::TagChanged { old: old.tag, new: new.tag } });
+    }
+
+    let attr = |element: &NodeElement, name: &str| element.attrs.as_ref().and_then(|attrs| attrs.get(name)).map(String::from);
+    let names: BTreeSet<&str> = old.attrs.iter().chain(&new.attrs).flat_map(|attrs| attrs.iter().map(|(name, _)| name)).collect();
+    for name in names {
+        let (old_value, new_value) = (attr(old, name), attr(new, name));
+        if old_value != new_value {
+            let kind = ChangeKind::AttrChanged { name: name.into(), old: old_value, new: new_value };
+            changes.push(Change { path: new_path.to_vec(), kind });
+        }
+    }
+
+    diff_children(
+        old.children.as_deref().unwrap_or_default(),
+        new.children.as_deref().unwrap_or_default(),
+        old_path,
+        new_path,
+        changes
+    );
+}
+
+
+/// Changes turning the `old` content into the `new` one.
+///
+/// Sibling nodes are matched by the longest common subsequence of equal nodes,
+/// very long differing runs of siblings are matched by position.
+/// Other equal nodes are reported as moved, the remaining nodes between matched ones
+/// are compared in order: text, tag and attribute changes are reported
+/// and their children are compared recursively.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::{build_content, content::{diff, ChangeKind}};
+/// let old = build_content(r#"[{"tag": "p", "children": ["Hello"]}]"#).unwrap();
+/// let new = build_content(r#"[{"tag": "p", "children": ["Hello, world"]}]"#).unwrap();
+/// let diff = diff(&old, &new);
+/// assert_eq!(diff.changes[0].path, vec![0, 0]);
+/// assert!(matches!(diff.changes[0].kind, ChangeKind::TextChanged { .. }));
+/// assert_eq!(diff.to_string(), "@@ [0, 0] @@\n- \"Hello\"\n+ \"Hello, world\"\n");
+/// ```
+pub fn diff(old: &[Node], new: &[Node]) -> ContentDiff {
+    let mut changes = vec![];
+    diff_children(old, new, &[], &[], &mut changes);
+    ContentDiff { changes }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+    use crate::types::{Node, NodeTag};
+
+    use super::{diff, Change, ChangeKind};
+
+    #[test]
+    fn diff_content() {
+        let old = build_content(r#"[
+            {"tag": "h3", "children": ["Title"]},
+            {"tag": "p", "children": ["First"]},
+            {"tag": "p", "children": ["Also first"]},
+            {"tag": "p", "children": ["Second ", {"tag": "a", "attrs": {"href": "/a"}, "children": ["link"]}]},
+            {"tag": "p", "children": ["Removed"]},
+            {"tag": "hr"}
+        ]"#).unwrap();
+        let new = build_content(r#"[
+            {"tag": "h4", "children": ["Title"]},
+            {"tag": "hr"},
+            {"tag": "p", "children": ["First"]},
+            {"tag": "p", "children": ["Also first"]},
+            {"tag": "p", "children": ["Second ", {"tag": "a", "attrs": {"href": "/b"}, "children": ["link"]}, "!"]}
+        ]"#).unwrap();
+
+        assert!(diff(&old, &old).is_empty());
+        let changes = diff(&old, &new).changes;
+        assert_eq!(changes, vec![
+            Change { path: vec![0], kind: ChangeKind::TagChanged { old: Some(NodeTag::H3), new: Some(NodeTag::H4) } },
+            Change { path: vec![1], kind: ChangeKind::Moved { from: vec![5], node: old[5].clone() } },
+            Change {
+                path: vec![4, 1],
+                kind: ChangeKind::AttrChanged { name: "href".into(), old: Some("/a".into()), new: Some("/b".into()) }
+            },
+            Change { path: vec![4, 2], kind: ChangeKind::Inserted(Node::String("!".into())) },
+            Change { path: vec![4], kind: ChangeKind::Removed(old[4].clone()) }
+        ]);
+
+        let rendered = diff(&old, &new).to_string();
+        assert!(rendered.starts_with("@@ [0] @@\n- <h3>\n+ <h4>\n@@ [1] @@\n> <hr> (moved from [5])\n"));
+        assert!(rendered.ends_with("@@ [4] @@\n- <p>Removed</p>\n"));
+    }
+
+    #[test]
+    fn diff_large_content() {
+        let old: Vec<Node> = (0..2000).map(|index| Node::String(index.to_string())).collect();
+
+        let mut new = old.clone();
+        new.insert(1000, Node::String("inserted".into()));
+        assert_eq!(diff(&old, &new).changes, vec![
+            Change { path: vec![1000], kind: ChangeKind::Inserted(Node::String("inserted".into())) }
+        ]);
+
+        let mut new = old.clone();
+        new[0] = Node::String("first".into());
+        new[1999] = Node::String("last".into());
+        assert_eq!(diff(&old, &new).changes, vec![
+            Change { path: vec![0], kind: ChangeKind::TextChanged { old: "0".into(), new: "first".into() } },
+            Change { path: vec![1999], kind: ChangeKind::TextChanged { old: "1999".into(), new: "last".into() } }
+        ]);
+    }
+}
@@ -0,0 +1,802 @@
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+
+use crate::error::TelegraphError;
+use crate::types::{Node, NodeElement, NodeElementAttr, NodeElementAttrs, NodeTag};
+use super::{element, push_node};
+use super::validate::allowed_attr;
+
+
+/// Elements which never have children
+const VOID_ELEMENTS: [&str; 15] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input",
+    "link", "meta", "param", "source", "track", "wbr", "keygen"
+];
+
+/// Elements whose content is not markup and is dropped entirely
+const RAW_TEXT_ELEMENTS: [&str; 4] = ["script", "style", "textarea", "title"];
+
+/// Document-level elements which are unwrapped without being reported
+const DOCUMENT_ELEMENTS: [&str; 6] = ["html", "head", "body", "meta", "link", "title"];
+
+/// Elements which implicitly close an open `p`
+const CLOSES_PARAGRAPH: [&str; 22] = [
+    "address", "article", "aside", "blockquote", "div", "dl", "fieldset", "figure",
+    "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6", "header", "hr", "ol", "p", "pre", "ul"
+];
+
+/// Unsupported block elements, which are unwrapped
+const BLOCK_ELEMENTS: [&str; 13] = [
+    "body", "dd", "dt", "html", "li", "main", "nav", "section", "table", "tbody", "td", "th", "tr"
+];
+
+
+enum Token {
+    Open { name: String, attrs: Vec<(String, String)>, self_closing: bool },
+    Close(String),
+    Text(String)
+}
+
+
+struct Cursor<'a> {
+    input: &'a str,
+    pos: usize
+}
+
+
+impl<'a> Cursor<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(ch) = self.peek() {
+            self.pos += ch.len_utf8();
+        }
+    }
+
+    fn eat_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> &'a str {
+        let start = self.pos;
+        while matches!(self.peek(), Some(ch) if predicate(ch)) {
+            self.bump();
+        }
+        &self.input[start..self.pos]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.eat_while(char::is_whitespace);
+    }
+}
+
+
+/// Parses an opening tag, `input` starts right after `<`.
+/// Returns the token and the number of consumed bytes.
+fn parse_open_tag(input: &str) -> (Token, usize) {
+    let mut cursor = Cursor { input, pos: 0 };
+    let name = cursor.eat_while(|ch| !ch.is_whitespace() && ch != '/' && ch != '>').to_ascii_lowercase();
+    let mut attrs = vec![];
+    let mut self_closing = false;
+
+    loop {
+        cursor.skip_whitespace();
+        match cursor.peek() {
+            None => break,
+            Some('>') => {
+                cursor.bump();
+                break;
+            },
+            Some('/') => {
+                cursor.bump();
+                self_closing = cursor.peek() == Some('>');
+            },
+            Some(_) => {
+                let attr = cursor.eat_while(|ch| !ch.is_whitespace() && !matches!(ch, '=' | '>' | '/'));
+                if attr.is_empty() {
+                    cursor.bump();
+                    continue;
+                }
+                let attr = attr.to_ascii_lowercase();
+                cursor.skip_whitespace();
+                let mut value = String::new();
+                if cursor.peek() == Some('=') {
+                    cursor.bump();
+                    cursor.skip_whitespace();
+                    value = match cursor.peek() {
+                        Some(quote @ ('"' | '\'')) => {
+                            cursor.bump();
+                            let value = cursor.eat_while(|ch| ch != quote);
+                            cursor.bump();
+                            decode_entities(value)
+                        },
+                        _ => decode_entities(cursor.eat_while(|ch| !ch.is_whitespace() && ch != '>'))
+                    };
+                }
+                attrs.push((attr, value));
+            }
+        }
+    }
+
+    (Token::Open { name, attrs, self_closing }, cursor.pos)
+}
+
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if rest.starts_with("</") && rest[2..].starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+            let end = rest.find('>').unwrap_or(rest.len());
+            let name = rest[2..end].trim().to_ascii_lowercase();
+            tokens.push(Token::Close(name));
+            rest = rest.get(end + 1..).unwrap_or("");
+        } else if rest.starts_with('<') && rest[1..].starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+            let (token, consumed) = parse_open_tag(&rest[1..]);
+            rest = &rest[1 + consumed..];
+            if let Token::Open { name, .. } = &token {
+                if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                    let close = format!("</{}", name);
+                    rest = match rest.to_ascii_lowercase().find(&close) {
+                        Some(start) => rest[start..].find('>').map_or("", |end| &rest[start + end + 1..]),
+                        None => ""
+                    };
+                    tokens.push(token);
+                    tokens.push(Token::Close(close[2..].to_string()));
+                    continue;
+                }
+            }
+            tokens.push(token);
+        } else {
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let end = rest[first..].find('<').map_or(rest.len(), |end| end + first);
+            tokens.push(Token::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(number) = entity.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?
+        };
+        return char::from_u32(code);
+    }
+    let ch = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '\u{2013}',
+        "mdash" => '\u{2014}',
+        "hellip" => '\u{2026}',
+        "laquo" => '\u{ab}',
+        "raquo" => '\u{bb}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201c}',
+        "rdquo" => '\u{201d}',
+        "copy" => '\u{a9}',
+        "reg" => '\u{ae}',
+        "trade" => '\u{2122}',
+        "times" => '\u{d7}',
+        "middot" => '\u{b7}',
+        "bull" => '\u{2022}',
+        _ => return None
+    };
+    Some(ch)
+}
+
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest[1..].find(';')
+            .filter(|end| *end <= 32)
+            .and_then(|end| decode_entity(&rest[1..end + 1]).map(|ch| (ch, end + 2)));
+        match decoded {
+            Some((ch, consumed)) => {
+                out.push(ch);
+                rest = &rest[consumed..];
+            },
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+
+/// Telegraph tag for the HTML element, `None` if the element is unwrapped.
+fn map_tag(name: &str) -> Option<NodeTag> {
+    match name {
+        "h1" | "h2" => Some(NodeTag::H3),
+        "h5" | "h6" => Some(NodeTag::H4),
+        "del" | "strike" => Some(NodeTag::S),
+        "ins" => Some(NodeTag::U),
+        "kbd" | "samp" | "tt" => Some(NodeTag::Code),
+        _ => NodeTag::from_name(name)
+    }
+}
+
+
+fn map_attr(tag: NodeTag, attrs: &[(String, String)]) -> Option<NodeElementAttrs> {
+    let find = |name: &str| attrs.iter()
+        .find(|(attr, _)| attr == name)
+        .map(|(_, value)| value.clone());
+    let attr = match tag {
+        NodeTag::A => find("href").map(NodeElementAttr::Href),
+        NodeTag::Img | NodeTag::Iframe | NodeTag::Video => find("src").map(NodeElementAttr::Src),
+        NodeTag::H3 | NodeTag::H4 => find("id").map(NodeElementAttr::ID),
+        _ => None
+    };
+    attr.map(NodeElementAttrs::from)
+}
+
+
+fn is_block_tag(tag: NodeTag) -> bool {
+    matches!(tag,
+        NodeTag::Aside | NodeTag::Blockquote | NodeTag::Figure | NodeTag::Figcaption |
+        NodeTag::H3 | NodeTag::H4 | NodeTag::Hr | NodeTag::Iframe | NodeTag::Li |
+        NodeTag::Ol | NodeTag::P | NodeTag::Pre | NodeTag::Ul | NodeTag::Video
+    )
+}
+
+
+pub(super) fn is_block(node: &Node) -> bool {
+    matches!(node, Node::NodeElement(NodeElement { tag: Some(tag), .. }) if is_block_tag(*tag))
+}
+
+
+fn is_whitespace(node: &Node) -> bool {
+    matches!(node, Node::String(text) if text.chars().all(|ch| ch.is_ascii_whitespace()))
+}
+
+
+/// Marker left around the content of an unwrapped block element,
+/// so the text of adjacent blocks is not glued together.
+fn boundary() -> Node {
+    Node::NodeElement(NodeElement::default())
+}
+
+
+fn is_boundary(node: &Node) -> bool {
+    matches!(node, Node::NodeElement(NodeElement { tag: None, .. }))
+}
+
+
+fn is_break(node: &Node) -> bool {
+    is_block(node) || is_boundary(node)
+}
+
+
+/// Drops whitespace around block elements and trims the edges of a block.
+fn normalize_block(children: Vec<Node>) -> Vec<Node> {
+    let mut result: Vec<Node> = vec![];
+    for (index, child) in children.iter().enumerate() {
+        if is_whitespace(child) {
+            let prev_block = index == 0 || is_break(&children[index - 1]);
+            let next_block = children.get(index + 1).is_none_or(is_break);
+            if prev_block || next_block {
+                continue;
+            }
+        }
+        result.push(child.clone());
+    }
+
+    if let Some(Node::String(text)) = result.first_mut() {
+        *text = text.trim_start_matches(|ch: char| ch.is_ascii_whitespace()).to_string();
+    }
+    if let Some(Node::String(text)) = result.last_mut() {
+        *text = text.trim_end_matches(|ch: char| ch.is_ascii_whitespace()).to_string();
+    }
+    result.retain(|node| !matches!(node, Node::String(text) if text.is_empty()));
+    result
+}
+
+
+/// Replaces boundaries with paragraphs wrapping the text runs between blocks at the top level
+/// and with `br` between the runs elsewhere.
+fn resolve_boundaries(children: Vec<Node>, top_level: bool) -> Vec<Node> {
+    if !children.iter().any(is_boundary) {
+        return children;
+    }
+
+    let mut result: Vec<Node> = vec![];
+    let mut run: Vec<Node> = vec![];
+    let flush = |result: &mut Vec<Node>, run: &mut Vec<Node>| {
+        let run = normalize_block(std::mem::take(run));
+        if run.is_empty() {
+            return;
+        }
+        if top_level {
+            result.push(element(NodeTag::P, None, run));
+        } else {
+            if result.last().is_some_and(|node| !is_block(node)) {
+                result.push(element(NodeTag::Br, None, vec![]));
+            }
+            for node in run {
+                push_node(result, node);
+            }
+        }
+    };
+    for child in children {
+        if is_boundary(&child) || (top_level && is_block(&child)) {
+            flush(&mut result, &mut run);
+            if !is_boundary(&child) {
+                result.push(child);
+            }
+        } else {
+            run.push(child);
+        }
+    }
+    flush(&mut result, &mut run);
+    result
+}
+
+
+struct Frame {
+    name: String,
+    tag: Option<NodeTag>,
+    attrs: Option<NodeElementAttrs>,
+    children: Vec<Node>
+}
+
+
+impl Frame {
+    fn is_block(&self) -> bool {
+        match self.tag {
+            Some(tag) => is_block_tag(tag),
+            None => CLOSES_PARAGRAPH.contains(&self.name.as_str()) || BLOCK_ELEMENTS.contains(&self.name.as_str())
+        }
+    }
+}
+
+
+struct TreeBuilder {
+    stack: Vec<Frame>,
+    unsupported: BTreeSet<String>
+}
+
+
+impl TreeBuilder {
+    fn new() -> Self {
+        TreeBuilder {
+            stack: vec![Frame { name: String::new(), tag: None, attrs: None, children: vec![] }],
+            unsupported: BTreeSet::new()
+        }
+    }
+
+    fn top(&mut self) -> &mut Frame {
+        self.stack.last_mut().expect("Root frame is never popped")
+    }
+
+    fn in_pre(&self) -> bool {
+        self.stack.iter().any(|frame| frame.tag == Some(NodeTag::Pre))
+    }
+
+    fn report(&mut self, name: &str) {
+        if !DOCUMENT_ELEMENTS.contains(&name) {
+            self.unsupported.insert(name.to_string());
+        }
+    }
+
+    fn text(&mut self, text: String) {
+        let text = if self.in_pre() {
+            text
+        } else {
+            let mut collapsed = String::with_capacity(text.len());
+            for ch in text.chars() {
+                if ch.is_ascii_whitespace() {
+                    if !collapsed.ends_with(' ') {
+                        collapsed.push(' ');
+                    }
+                } else {
+                    collapsed.push(ch);
+                }
+            }
+            collapsed
+        };
+        if !text.is_empty() {
+            push_node(&mut self.top().children, Node::String(text));
+        }
+    }
+
+    fn open(&mut self, name: String, attrs: Vec<(String, String)>, self_closing: bool) {
+        if name == "source" {
+            let top = self.top();
+            if top.tag == Some(NodeTag::Video) && top.attrs.is_none() {
+                top.attrs = map_attr(NodeTag::Video, &attrs);
+            }
+            return;
+        }
+
+        if CLOSES_PARAGRAPH.contains(&name.as_str()) && self.top().name == "p" {
+            self.close("p");
+        }
+        if name == "li" {
+            let list = self.stack.iter().rposition(|frame| frame.name == "ul" || frame.name == "ol");
+            let item = self.stack.iter().rposition(|frame| frame.name == "li");
+            if let Some(item) = item.filter(|item| list.is_none_or(|list| *item > list)) {
+                let name = self.stack[item].name.clone();
+                self.close(&name);
+            }
+        }
+
+        let tag = map_tag(&name);
+        if tag.is_none() {
+            self.report(&name);
+        }
+
+        if VOID_ELEMENTS.contains(&name.as_str()) || tag.is_some_and(|tag| tag.is_void()) {
+            if let Some(tag) = tag {
+                push_node(&mut self.top().children, element(tag, map_attr(tag, &attrs), vec![]));
+            }
+            return;
+        }
+
+        let frame = Frame {
+            attrs: tag.and_then(|tag| map_attr(tag, &attrs)),
+            name,
+            tag,
+            children: vec![]
+        };
+        self.stack.push(frame);
+        if self_closing {
+            let name = self.top().name.clone();
+            self.close(&name);
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        let position = match self.stack.iter().skip(1).rposition(|frame| frame.name == name) {
+            Some(position) => position + 1,
+            None => return
+        };
+        while self.stack.len() > position {
+            self.finish();
+        }
+    }
+
+    fn finish(&mut self) {
+        let frame = self.stack.pop().expect("Root frame is never finished");
+        let separate = frame.is_block() && !DOCUMENT_ELEMENTS.contains(&frame.name.as_str());
+        let children = if frame.tag == Some(NodeTag::Pre) || !frame.is_block() {
+            frame.children
+        } else {
+            normalize_block(frame.children)
+        };
+        let parent = self.top();
+        match frame.tag {
+            Some(tag) => push_node(&mut parent.children, element(tag, frame.attrs, resolve_boundaries(children, false))),
+            None => {
+                if separate {
+                    parent.children.push(boundary());
+                }
+                for child in children {
+                    push_node(&mut parent.children, child);
+                }
+                if separate {
+                    parent.children.push(boundary());
+                }
+            }
+        }
+    }
+
+    fn build(mut self) -> (Vec<Node>, BTreeSet<String>) {
+        while self.stack.len() > 1 {
+            self.finish();
+        }
+        let root = self.stack.pop().expect("Root frame is never popped");
+        (resolve_boundaries(normalize_block(root.children), true), self.unsupported)
+    }
+}
+
+
+fn parse(html: &str) -> (Vec<Node>, BTreeSet<String>) {
+    let mut builder = TreeBuilder::new();
+    for token in tokenize(html) {
+        match token {
+            Token::Open { name, attrs, self_closing } => builder.open(name, attrs, self_closing),
+            Token::Close(name) => builder.close(&name),
+            Token::Text(text) => builder.text(text)
+        }
+    }
+    builder.build()
+}
+
+
+/// Tag or text of an inline HTML fragment, see [`inline_html`]
+#[cfg(any(test, feature = "markdown"))]
+pub(super) enum InlineHtml {
+    /// Opening tag, `tag` is `None` for unsupported and block elements, whose children are kept
+    Open { name: String, tag: Option<NodeTag>, attrs: Option<NodeElementAttrs> },
+    /// Void element like `br` or `img`
+    Void(Node),
+    Close(String),
+    Text(String)
+}
+
+
+/// Splits an inline HTML fragment, like the raw HTML of a Markdown paragraph,
+/// into tags mapped as in [`from_html`] and text.
+#[cfg(any(test, feature = "markdown"))]
+pub(super) fn inline_html(html: &str) -> Vec<InlineHtml> {
+    tokenize(html).into_iter().filter_map(|token| match token {
+        Token::Open { name, attrs, self_closing } => {
+            let tag = map_tag(&name);
+            if VOID_ELEMENTS.contains(&name.as_str()) || tag.is_some_and(|tag| tag.is_void()) {
+                return tag.map(|tag| InlineHtml::Void(element(tag, map_attr(tag, &attrs), vec![])));
+            }
+            if self_closing {
+                return None;
+            }
+            let tag = tag.filter(|tag| !is_block_tag(*tag));
+            let attrs = tag.and_then(|tag| map_attr(tag, &attrs));
+            Some(InlineHtml::Open { name, tag, attrs })
+        },
+        Token::Close(name) => Some(InlineHtml::Close(name)),
+        Token::Text(text) => Some(InlineHtml::Text(text))
+    }).collect()
+}
+
+
+/// Build page content from HTML.
+///
+/// Tags which Telegraph does not support are dropped, their text is kept.
+/// Text of unsupported block elements like `div` or `td` becomes a separate paragraph,
+/// or is separated by `br` inside other elements.
+/// `h1`/`h2` are converted to `h3`, `h5`/`h6` to `h4`, `del`/`strike` to `s`.
+/// Content of `script` and `style` is removed.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::content::from_html;
+/// let content = from_html("<h3>Hello world</h3><p>Some <b>bold</b> text</p>").unwrap();
+/// assert_eq!(content.len(), 2);
+/// ```
+pub fn from_html(html: &str) -> Result<Vec<Node>, TelegraphError> {
+    Ok(parse(html).0)
+}
+
+
+/// Build page content from HTML, reporting tags which Telegraph does not support.
+///
+/// Returns [`TelegraphError::ContentError`] naming every unsupported tag.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::content::from_html_strict;
+/// assert!(from_html_strict("<p>Hello world</p>").is_ok());
+/// assert!(from_html_strict("<table><tr><td>Cell</td></tr></table>").is_err());
+/// ```
+pub fn from_html_strict(html: &str) -> Result<Vec<Node>, TelegraphError> {
+    let (content, unsupported) = parse(html);
+    if unsupported.is_empty() {
+        Ok(content)
+    } else {
+        let tags: Vec<String> = unsupported.into_iter().collect();
+        Err(TelegraphError::ContentError(format!("Unsupported tags: {}", tags.join(", "))))
+    }
+}
+
+
+fn escape(text: &str, attr: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' if attr => out.push_str("&quot;"),
+            '\u{a0}' => out.push_str("&nbsp;"),
+            _ => out.push(ch)
+        }
+    }
+    out
+}
+
+
+fn write_node<W: Write>(writer: &mut W, node: &Node) -> io::Result<()> {
+    let element = match node {
+        Node::String(text) => return writer.write_all(escape(text, false).as_bytes()),
+        Node::NodeElement(element) => element
+    };
+    let tag = match element.tag {
+        Some(tag) => tag,
+        None => return write_nodes(writer, element.children.as_deref().unwrap_or_default())
+    };
+
+    write!(writer, "<{}", tag.as_str())?;
+    // Only attributes Telegraph accepts are written, unknown keys of untrusted content could inject markup
+    for (name, value) in element.attrs.iter().flat_map(NodeElementAttrs::iter).filter(|(name, _)| allowed_attr(tag, name)) {
+        write!(writer, " {}=\"{}\"", name, escape(value, true))?;
+    }
+    if tag == NodeTag::Iframe {
+        writer.write_all(b" frameborder=\"0\" allowfullscreen")?;
+    }
+    writer.write_all(b">")?;
+    if tag.is_void() {
+        return Ok(());
+    }
+    write_nodes(writer, element.children.as_deref().unwrap_or_default())?;
+    write!(writer, "</{}>", tag.as_str())
+}
+
+
+fn write_nodes<W: Write>(writer: &mut W, nodes: &[Node]) -> io::Result<()> {
+    for node in nodes {
+        write_node(writer, node)?;
+    }
+    Ok(())
+}
+
+
+/// Render page content as HTML into `writer`.
+///
+/// Text nodes are escaped. Only `href` of `a`, `src` of `img`, `iframe` and `video`
+/// and `id` of `h3` and `h4` are emitted, other attributes are skipped.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::{build_content, content::write_html};
+/// let content = build_content(r#"[{"tag": "p", "children": ["Hello world"]}]"#).unwrap();
+/// let mut html = vec![];
+/// write_html(&mut html, &content).unwrap();
+/// assert_eq!(html, b"<p>Hello world</p>");
+/// ```
+pub fn write_html<W: Write>(writer: &mut W, content: &[Node]) -> io::Result<()> {
+    write_nodes(writer, content)
+}
+
+
+/// Render page content as HTML string.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::{build_content, content::to_html};
+/// let content = build_content(r#"[{"tag": "a", "attrs": {"href": "/x?a=1&b=2"}, "children": ["<link>"]}]"#).unwrap();
+/// assert_eq!(to_html(&content), r#"<a href="/x?a=1&amp;b=2">&lt;link&gt;</a>"#);
+/// ```
+pub fn to_html(content: &[Node]) -> String {
+    let mut html = vec![];
+    write_html(&mut html, content).expect("Writing to Vec never fails");
+    String::from_utf8(html).expect("Rendered HTML is valid UTF-8")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+    use crate::error::TelegraphError;
+
+    use super::{from_html, from_html_strict, to_html};
+
+    #[test]
+    fn html_to_nodes() {
+        let html = r#"
+            <h1>Title</h1>
+            <p>Some <b>bold</b> &amp; <a href="https://telegra.ph">link</a></p>
+            <img src="/file/1.jpg">
+        "#;
+        let expected = build_content(r#"[
+            {"tag": "h3", "attrs": null, "children": ["Title"]},
+            {"tag": "p", "attrs": null, "children": [
+                "Some ",
+                {"tag": "b", "attrs": null, "children": ["bold"]},
+                " & ",
+                {"tag": "a", "attrs": {"href": "https://telegra.ph"}, "children": ["link"]}
+            ]},
+            {"tag": "img", "attrs": {"src": "/file/1.jpg"}, "children": null}
+        ]"#).unwrap();
+        assert_eq!(from_html(html).unwrap(), expected);
+    }
+
+    #[test]
+    fn html_implicit_close() {
+        let html = "<ul><li>One<li>Two</ul><p>First<p>Second";
+        let expected = build_content(r#"[
+            {"tag": "ul", "children": [
+                {"tag": "li", "children": ["One"]},
+                {"tag": "li", "children": ["Two"]}
+            ]},
+            {"tag": "p", "children": ["First"]},
+            {"tag": "p", "children": ["Second"]}
+        ]"#).unwrap();
+        assert_eq!(from_html(html).unwrap(), expected);
+    }
+
+    #[test]
+    fn html_preformatted() {
+        let html = "<pre>fn main() {\n    42\n}</pre>Привет <i>мир</i>";
+        let expected = build_content(r#"[
+            {"tag": "pre", "children": ["fn main() {\n    42\n}"]},
+            "Привет ",
+            {"tag": "i", "children": ["мир"]}
+        ]"#).unwrap();
+        assert_eq!(from_html(html).unwrap(), expected);
+    }
+
+    #[test]
+    fn html_unsupported_tags() {
+        let html = "<div><span>Text</span><script>alert(1)</script></div>";
+        let expected = build_content(r#"[{"tag": "p", "children": ["Text"]}]"#).unwrap();
+        assert_eq!(from_html(html).unwrap(), expected);
+
+        let error = from_html_strict(html).unwrap_err();
+        assert!(matches!(error, TelegraphError::ContentError(error) if error == "Unsupported tags: div, script, span"));
+    }
+
+    #[test]
+    fn html_unwrapped_blocks() {
+        let html = "<div>One</div><div>Two</div><p>A</p>Tail";
+        let expected = build_content(r#"[
+            {"tag": "p", "children": ["One"]},
+            {"tag": "p", "children": ["Two"]},
+            {"tag": "p", "children": ["A"]},
+            {"tag": "p", "children": ["Tail"]}
+        ]"#).unwrap();
+        assert_eq!(from_html(html).unwrap(), expected);
+
+        let html = "<table><tr><td>a</td><td>b</td></tr></table><blockquote><div>c</div> <div>d</div></blockquote>";
+        let expected = build_content(r#"[
+            {"tag": "p", "children": ["a"]},
+            {"tag": "p", "children": ["b"]},
+            {"tag": "blockquote", "children": ["c", {"tag": "br"}, "d"]}
+        ]"#).unwrap();
+        assert_eq!(from_html(html).unwrap(), expected);
+    }
+
+    #[test]
+    fn nodes_to_html() {
+        let content = build_content(r#"[
+            {"tag": "h3", "attrs": {"id": "Title"}, "children": ["Title"]},
+            {"tag": "p", "children": ["1 < 2 & ", {"tag": "b", "children": ["bold"]}, {"tag": "br"}]},
+            {"tag": "figure", "children": [
+                {"tag": "img", "attrs": {"src": "/file/1.jpg"}},
+                {"tag": "figcaption", "children": ["Caption"]}
+            ]},
+            {"tag": "video", "attrs": {"src": "/file/1.mp4"}}
+        ]"#).unwrap();
+        let html = to_html(&content);
+        assert_eq!(html, concat!(
+            "<h3 id=\"Title\">Title</h3>",
+            "<p>1 &lt; 2 &amp; <b>bold</b><br></p>",
+            "<figure><img src=\"/file/1.jpg\"><figcaption>Caption</figcaption></figure>",
+            "<video src=\"/file/1.mp4\"></video>"
+        ));
+        assert_eq!(from_html(&html).unwrap(), content);
+    }
+
+    #[test]
+    fn html_skips_unknown_attrs() {
+        let content = build_content(r#"[
+            {"tag": "p", "attrs": {"onclick": "alert(1)", "x\"><script>alert(2)</script><b": "1"}, "children": ["hi"]},
+            {"tag": "img", "attrs": {"src": "/file/1.jpg", "onerror": "alert(3)"}},
+            {"tag": "a", "attrs": {"href": "/a", "src": "/b", "onmouseover": "alert(4)"}, "children": ["link"]},
+            {"tag": "h4", "attrs": {"id": "\"><script>"}, "children": ["Title"]}
+        ]"#).unwrap();
+        assert_eq!(to_html(&content), concat!(
+            "<p>hi</p>",
+            "<img src=\"/file/1.jpg\">",
+            "<a href=\"/a\">link</a>",
+            "<h4 id=\"&quot;&gt;&lt;script&gt;\">Title</h4>"
+        ));
+    }
+}
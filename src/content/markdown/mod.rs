@@ -0,0 +1,7 @@
+#[cfg(any(test, feature = "markdown"))]
+mod parse;
+mod render;
+
+#[cfg(any(test, feature = "markdown"))]
+pub use parse::from_markdown;
+pub use render::to_markdown;
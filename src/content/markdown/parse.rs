@@ -0,0 +1,327 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use crate::error::TelegraphError;
+use crate::types::{Node, NodeElementAttr, NodeElementAttrs, NodeTag};
+use crate::content::{element, from_html, push_node};
+use crate::content::html::{inline_html, InlineHtml};
+
+
+enum Kind {
+    Element,
+    Paragraph,
+    Image,
+    Html(String),
+    /// Element opened by inline HTML, closed by its closing tag or the end of the enclosing element
+    InlineHtml(String),
+    Unwrap
+}
+
+
+struct Frame {
+    kind: Kind,
+    tag: Option<NodeTag>,
+    attrs: Option<NodeElementAttrs>,
+    children: Vec<Node>
+}
+
+
+impl Frame {
+    fn new(kind: Kind, tag: Option<NodeTag>, attrs: Option<NodeElementAttrs>) -> Self {
+        Frame { kind, tag, attrs, children: vec![] }
+    }
+}
+
+
+fn is_figure(node: &Node) -> bool {
+    matches!(node, Node::NodeElement(element) if element.tag == Some(NodeTag::Figure))
+}
+
+
+fn text_of(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::String(text) => out.push_str(text),
+            Node::NodeElement(element) => text_of(element.children.as_deref().unwrap_or_default(), out)
+        }
+    }
+}
+
+
+struct TreeBuilder {
+    stack: Vec<Frame>
+}
+
+
+impl TreeBuilder {
+    fn top(&mut self) -> &mut Frame {
+        self.stack.last_mut().expect("Root frame is never popped")
+    }
+
+    fn push(&mut self, node: Node) {
+        push_node(&mut self.top().children, node);
+    }
+
+    fn start(&mut self, tag: Tag) {
+        let frame = match tag {
+            Tag::Paragraph => Frame::new(Kind::Paragraph, Some(NodeTag::P), None),
+            Tag::Heading { level: HeadingLevel::H1 | HeadingLevel::H2, .. } => Frame::new(Kind::Element, Some(NodeTag::H3), None),
+            Tag::Heading { .. } => Frame::new(Kind::Element, Some(NodeTag::H4), None),
+            Tag::BlockQuote(_) => Frame::new(Kind::Element, Some(NodeTag::Blockquote), None),
+            Tag::CodeBlock(_) => Frame::new(Kind::Element, Some(NodeTag::Pre), None),
+            Tag::HtmlBlock => Frame::new(Kind::Html(String::new()), None, None),
+            Tag::List(Some(_)) => Frame::new(Kind::Element, Some(NodeTag::Ol), None),
+            Tag::List(None) => Frame::new(Kind::Element, Some(NodeTag::Ul), None),
+            Tag::Item => Frame::new(Kind::Element, Some(NodeTag::Li), None),
+            Tag::Emphasis => Frame::new(Kind::Element, Some(NodeTag::Em), None),
+            Tag::Strong => Frame::new(Kind::Element, Some(NodeTag::Strong), None),
+            Tag::Strikethrough => Frame::new(Kind::Element, Some(NodeTag::S), None),
+            Tag::Link { dest_url, .. } => Frame::new(Kind::Element, Some(NodeTag::A), Some(NodeElementAttr::Href(dest_url.to_string()).into())),
+            Tag::Image { dest_url, .. } => Frame::new(Kind::Image, Some(NodeTag::Img), Some(NodeElementAttr::Src(dest_url.to_string()).into())),
+            _ => Frame::new(Kind::Unwrap, None, None)
+        };
+        self.stack.push(frame);
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        while matches!(self.top().kind, Kind::InlineHtml(_)) {
+            self.close_inline();
+        }
+        let mut frame = self.stack.pop().expect("Unbalanced markdown events");
+        if let TagEnd::CodeBlock = tag {
+            if let Some(Node::String(text)) = frame.children.last_mut() {
+                if text.ends_with('\n') {
+                    text.pop();
+                }
+            }
+        }
+        self.finish(frame);
+    }
+
+    fn close_inline(&mut self) {
+        let frame = self.stack.pop().expect("Inline HTML frame is above the root");
+        self.finish(frame);
+    }
+
+    /// Inline HTML tags open and close elements, closing tags
+    /// without an element opened in the same Markdown element are ignored.
+    fn inline_html(&mut self, html: &str) {
+        for item in inline_html(html) {
+            match item {
+                InlineHtml::Open { name, tag, attrs } => self.stack.push(Frame::new(Kind::InlineHtml(name), tag, attrs)),
+                InlineHtml::Void(node) => self.push(node),
+                InlineHtml::Text(text) => self.push(Node::String(text)),
+                InlineHtml::Close(name) => {
+                    let depth = self.stack.iter()
+                        .rev()
+                        .take_while(|frame| matches!(frame.kind, Kind::InlineHtml(_)))
+                        .position(|frame| matches!(&frame.kind, Kind::InlineHtml(open) if *open == name));
+                    for _ in 0..depth.map_or(0, |depth| depth + 1) {
+                        self.close_inline();
+                    }
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self, frame: Frame) {
+        match frame.kind {
+            Kind::Element => {
+                let tag = frame.tag.expect("Element frame has a tag");
+                self.push(element(tag, frame.attrs, frame.children));
+            },
+            Kind::Image if !self.in_top_paragraph() => self.push(element(NodeTag::Img, frame.attrs, vec![])),
+            Kind::Image => {
+                let mut caption = String::new();
+                text_of(&frame.children, &mut caption);
+                let mut children = vec![element(NodeTag::Img, frame.attrs, vec![])];
+                if !caption.is_empty() {
+                    children.push(element(NodeTag::Figcaption, None, vec![Node::String(caption)]));
+                }
+                self.push(element(NodeTag::Figure, None, children));
+            },
+            Kind::Paragraph => self.paragraph(frame.children),
+            Kind::Html(html) => {
+                // Tolerant HTML conversion never fails
+                for node in from_html(&html).unwrap_or_default() {
+                    self.push(node);
+                }
+            },
+            Kind::InlineHtml(_) if frame.tag.is_some() => {
+                self.push(element(frame.tag.expect("Checked by the guard"), frame.attrs, frame.children));
+            },
+            Kind::InlineHtml(_) | Kind::Unwrap => {
+                for node in frame.children {
+                    self.push(node);
+                }
+            }
+        }
+    }
+
+    /// Figures are only allowed at the top level, so images
+    /// nested deeper are kept as plain `img` without a caption.
+    fn in_top_paragraph(&self) -> bool {
+        self.stack.len() == 2 && matches!(self.stack[1].kind, Kind::Paragraph)
+    }
+
+    /// Paragraphs inside list items and quotes are inlined, separated by `br`,
+    /// figures are moved out of paragraphs.
+    fn paragraph(&mut self, children: Vec<Node>) {
+        let top = self.top();
+        if matches!(top.tag, Some(NodeTag::Li | NodeTag::Blockquote)) {
+            if !top.children.is_empty() {
+                top.children.push(element(NodeTag::Br, None, vec![]));
+            }
+            for node in children {
+                push_node(&mut top.children, node);
+            }
+            return;
+        }
+
+        let mut inline = vec![];
+        for node in children {
+            if is_figure(&node) {
+                if inline.iter().any(|node| !matches!(node, Node::String(text) if text.trim().is_empty())) {
+                    top.children.push(element(NodeTag::P, None, std::mem::take(&mut inline)));
+                }
+                inline.clear();
+                top.children.push(node);
+            } else {
+                push_node(&mut inline, node);
+            }
+        }
+        if !inline.is_empty() {
+            top.children.push(element(NodeTag::P, None, inline));
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) | Event::InlineMath(text) | Event::DisplayMath(text) => self.push(Node::String(text.to_string())),
+            Event::Code(code) => self.push(element(NodeTag::Code, None, vec![Node::String(code.to_string())])),
+            Event::Html(html) | Event::InlineHtml(html) => {
+                if let Kind::Html(buffer) = &mut self.top().kind {
+                    buffer.push_str(&html);
+                } else {
+                    self.inline_html(&html);
+                }
+            },
+            Event::FootnoteReference(name) => self.push(Node::String(format!("[{}]", name))),
+            Event::SoftBreak => self.push(Node::String(" ".into())),
+            Event::HardBreak => self.push(element(NodeTag::Br, None, vec![])),
+            Event::Rule => self.push(element(NodeTag::Hr, None, vec![])),
+            Event::TaskListMarker(checked) => self.push(Node::String(if checked { "[x] " } else { "[ ] " }.into()))
+        }
+    }
+}
+
+
+/// Build page content from Markdown (CommonMark).
+///
+/// Headings of level 1 and 2 are converted to `h3`, the others to `h4`,
+/// code blocks to `pre`, top level images to `figure` with `img` and `figcaption`
+/// containing the image alt text, nested images to `img`. Raw HTML blocks are converted with [`from_html`],
+/// supported inline tags like `<u>`, `<b>` or `<br>` inside paragraphs are kept as elements.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::content::from_markdown;
+/// let content = from_markdown("# Title\n\nSome **bold** text").unwrap();
+/// assert_eq!(content.len(), 2);
+/// ```
+pub fn from_markdown(markdown: &str) -> Result<Vec<Node>, TelegraphError> {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut builder = TreeBuilder { stack: vec![Frame::new(Kind::Unwrap, None, None)] };
+    for event in Parser::new_ext(markdown, options) {
+        builder.event(event);
+    }
+    if builder.stack.len() != 1 {
+        return Err(TelegraphError::ContentError("Unbalanced markdown events".into()));
+    }
+    Ok(builder.stack.pop().map(|root| root.children).unwrap_or_default())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+    use crate::content::validate;
+
+    use super::from_markdown;
+
+    #[test]
+    fn markdown_to_nodes() {
+        let markdown = "# Title\n\n### Subtitle\n\nSome *em* **strong** ~~s~~ `code` [link](https://telegra.ph)\n\n---\n";
+        let expected = build_content(r#"[
+            {"tag": "h3", "children": ["Title"]},
+            {"tag": "h4", "children": ["Subtitle"]},
+            {"tag": "p", "children": [
+                "Some ",
+                {"tag": "em", "children": ["em"]},
+                " ",
+                {"tag": "strong", "children": ["strong"]},
+                " ",
+                {"tag": "s", "children": ["s"]},
+                " ",
+                {"tag": "code", "children": ["code"]},
+                " ",
+                {"tag": "a", "attrs": {"href": "https://telegra.ph"}, "children": ["link"]}
+            ]},
+            {"tag": "hr"}
+        ]"#).unwrap();
+        assert_eq!(from_markdown(markdown).unwrap(), expected);
+    }
+
+    #[test]
+    fn markdown_blocks() {
+        let markdown = "> Quote\n> line\n\n- One\n- Two\n\n1. First\n\n```rust\nfn main() {}\n```\n\n![Alt text](/file/1.jpg)\n";
+        let expected = build_content(r#"[
+            {"tag": "blockquote", "children": ["Quote line"]},
+            {"tag": "ul", "children": [
+                {"tag": "li", "children": ["One"]},
+                {"tag": "li", "children": ["Two"]}
+            ]},
+            {"tag": "ol", "children": [{"tag": "li", "children": ["First"]}]},
+            {"tag": "pre", "children": ["fn main() {}"]},
+            {"tag": "figure", "children": [
+                {"tag": "img", "attrs": {"src": "/file/1.jpg"}},
+                {"tag": "figcaption", "children": ["Alt text"]}
+            ]}
+        ]"#).unwrap();
+        assert_eq!(from_markdown(markdown).unwrap(), expected);
+    }
+
+    #[test]
+    fn markdown_nested_images() {
+        let markdown = "[![img](/file/a.png)](https://x)\n\n- ![alt](/file/a.png)\n";
+        let expected = build_content(r#"[
+            {"tag": "p", "children": [
+                {"tag": "a", "attrs": {"href": "https://x"}, "children": [{"tag": "img", "attrs": {"src": "/file/a.png"}}]}
+            ]},
+            {"tag": "ul", "children": [{"tag": "li", "children": [{"tag": "img", "attrs": {"src": "/file/a.png"}}]}]}
+        ]"#).unwrap();
+        let content = from_markdown(markdown).unwrap();
+        assert_eq!(content, expected);
+        assert_eq!(validate(&content), Ok(()));
+    }
+
+    #[test]
+    fn markdown_inline_html() {
+        let markdown = "Some <u>under *lined*</u> and <b>bold</b><br>next <span>plain</span> <a href=\"/a\">link</a>\n\n\
+            **<i>unclosed** text</i>\n";
+        let expected = build_content(r#"[
+            {"tag": "p", "children": [
+                "Some ",
+                {"tag": "u", "children": ["under ", {"tag": "em", "children": ["lined"]}]},
+                " and ",
+                {"tag": "b", "children": ["bold"]},
+                {"tag": "br"},
+                "next plain ",
+                {"tag": "a", "attrs": {"href": "/a"}, "children": ["link"]}
+            ]},
+            {"tag": "p", "children": [{"tag": "strong", "children": [{"tag": "i", "children": ["unclosed"]}]}, " text"]}
+        ]"#).unwrap();
+        assert_eq!(from_markdown(markdown).unwrap(), expected);
+    }
+}
@@ -0,0 +1,325 @@
+use crate::types::{Node, NodeElement, NodeElementAttrs, NodeTag};
+use crate::content::to_html;
+use crate::content::html::is_block;
+
+
+fn children(element: &NodeElement) -> &[Node] {
+    element.children.as_deref().unwrap_or_default()
+}
+
+
+fn src(element: &NodeElement) -> &str {
+    element.attrs.as_ref().and_then(NodeElementAttrs::src).unwrap_or_default()
+}
+
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '&' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '~' | '#' | '|') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+
+/// Escapes list markers at the start of a block.
+fn escape_block_start(text: String) -> String {
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    let rest = &text[digits..];
+    if (digits == 0 && (rest.starts_with("- ") || rest.starts_with("+ ") || rest == "-" || rest == "+"))
+        || (digits > 0 && (rest.starts_with(". ") || rest.starts_with(") ") || rest == "." || rest == ")"))
+    {
+        format!("{}\\{}", &text[..digits], &text[digits..])
+    } else {
+        text
+    }
+}
+
+
+/// Wraps `code` in a backtick run longer than any run inside it.
+fn code_span(code: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in code.chars() {
+        current = if ch == '`' { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    let fence = "`".repeat(longest + 1);
+    if code.starts_with('`') || code.ends_with('`') {
+        format!("{} {} {}", fence, code, fence)
+    } else {
+        format!("{}{}{}", fence, code, fence)
+    }
+}
+
+
+fn text_of(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::String(text) => out.push_str(text),
+            Node::NodeElement(element) => text_of(children(element), out)
+        }
+    }
+}
+
+
+fn wrap(marker: &str, nodes: &[Node]) -> String {
+    let inner = inline(nodes);
+    if inner.trim().is_empty() {
+        inner
+    } else {
+        format!("{}{}{}", marker, inner, marker)
+    }
+}
+
+
+fn inline(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        let element = match node {
+            Node::String(text) => {
+                out.push_str(&escape(text));
+                continue;
+            },
+            Node::NodeElement(element) => element
+        };
+        let rendered = match element.tag {
+            Some(NodeTag::B | NodeTag::Strong) => wrap("**", children(element)),
+            Some(NodeTag::Em | NodeTag::I) => wrap("*", children(element)),
+            Some(NodeTag::S) => wrap("~~", children(element)),
+            Some(NodeTag::U) => format!("<u>{}</u>", inline(children(element))),
+            Some(NodeTag::Code) => {
+                let mut code = String::new();
+                text_of(children(element), &mut code);
+                code_span(&code)
+            },
+            Some(NodeTag::A) => {
+                let href = element.attrs.as_ref().and_then(NodeElementAttrs::href).unwrap_or_default();
+                format!("[{}]({})", inline(children(element)), link_destination(href))
+            },
+            Some(NodeTag::Img) => format!("![]({})", link_destination(src(element))),
+            Some(NodeTag::Br) => "\\\n".into(),
+            Some(NodeTag::Iframe | NodeTag::Video) => {
+                let src = src(element);
+                format!("[{}]({})", escape(src), link_destination(src))
+            },
+            _ => inline(children(element))
+        };
+        out.push_str(&rendered);
+    }
+    out
+}
+
+
+fn link_destination(url: &str) -> String {
+    if url.is_empty() || url.contains([' ', '(', ')', '<', '>']) {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
+
+
+fn indent(text: &str, width: usize) -> String {
+    let padding = " ".repeat(width);
+    text.lines()
+        .map(|line| if line.is_empty() { String::new() } else { format!("{}{}", padding, line) })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+
+fn list(items: &[Node], ordered: bool) -> String {
+    let mut out = vec![];
+    for (index, item) in items.iter().enumerate() {
+        let marker = if ordered { format!("{}. ", index + 1) } else { "- ".to_string() };
+        let nodes = match item {
+            Node::NodeElement(element) if element.tag == Some(NodeTag::Li) => children(element),
+            _ => std::slice::from_ref(item)
+        };
+        let split = nodes.iter().position(is_block).unwrap_or(nodes.len());
+        let mut rendered = format!("{}{}", marker, escape_block_start(inline(&nodes[..split])).trim());
+        if split < nodes.len() {
+            rendered.push('\n');
+            rendered.push_str(&indent(&blocks(&nodes[split..]), marker.len()));
+        }
+        out.push(rendered);
+    }
+    out.join("\n")
+}
+
+
+/// HTML fallback for elements without Markdown syntax.
+fn html_block(node: &Node) -> String {
+    let html = to_html(std::slice::from_ref(node));
+    // `video` is not a CommonMark HTML block tag, its open tag must be on its own line
+    match html.strip_prefix("<video") {
+        Some(rest) => match rest.find('>') {
+            Some(end) => format!("<video{}\n{}", &rest[..=end], &rest[end + 1..]),
+            None => html
+        },
+        None => html
+    }
+}
+
+
+fn block(node: &Node) -> String {
+    let element = match node {
+        Node::NodeElement(element) => element,
+        Node::String(_) => return escape_block_start(inline(std::slice::from_ref(node)))
+    };
+    match element.tag {
+        Some(NodeTag::P) => escape_block_start(inline(children(element))),
+        Some(NodeTag::H3) => format!("## {}", inline(children(element))),
+        Some(NodeTag::H4) => format!("### {}", inline(children(element))),
+        Some(NodeTag::Hr) => "---".into(),
+        Some(NodeTag::Pre) => {
+            let mut code = String::new();
+            text_of(children(element), &mut code);
+            let mut fence = "```".to_string();
+            while code.contains(&fence) {
+                fence.push('`');
+            }
+            format!("{}\n{}\n{}", fence, code, fence)
+        },
+        Some(NodeTag::Blockquote) => blocks(children(element))
+            .lines()
+            .map(|line| if line.is_empty() { ">".to_string() } else { format!("> {}", line) })
+            .collect::<Vec<String>>()
+            .join("\n"),
+        Some(NodeTag::Ul) => list(children(element), false),
+        Some(NodeTag::Ol) => list(children(element), true),
+        Some(NodeTag::Li) => list(std::slice::from_ref(node), false),
+        Some(NodeTag::Figure) => {
+            let image = children(element).iter().find_map(|child| match child {
+                Node::NodeElement(child) if child.tag == Some(NodeTag::Img) => Some(child),
+                _ => None
+            });
+            let embed = children(element).iter().any(|child| matches!(child,
+                Node::NodeElement(child) if matches!(child.tag, Some(NodeTag::Video | NodeTag::Iframe))
+            ));
+            match image {
+                Some(image) if !embed => {
+                    let mut caption = String::new();
+                    for child in children(element) {
+                        if let Node::NodeElement(child) = child {
+                            if child.tag == Some(NodeTag::Figcaption) {
+                                text_of(children(child), &mut caption);
+                            }
+                        }
+                    }
+                    format!("![{}]({})", escape(&caption), link_destination(src(image)))
+                },
+                _ => html_block(node)
+            }
+        },
+        Some(NodeTag::Aside | NodeTag::Iframe | NodeTag::Video | NodeTag::Figcaption) => html_block(node),
+        _ => escape_block_start(inline(std::slice::from_ref(node)))
+    }
+}
+
+
+fn blocks(nodes: &[Node]) -> String {
+    let mut out: Vec<String> = vec![];
+    let mut paragraph: Vec<Node> = vec![];
+    for node in nodes {
+        if is_block(node) {
+            if !paragraph.is_empty() {
+                out.push(escape_block_start(inline(&paragraph)).trim().to_string());
+                paragraph.clear();
+            }
+            out.push(block(node));
+        } else {
+            paragraph.push(node.clone());
+        }
+    }
+    if !paragraph.is_empty() {
+        out.push(escape_block_start(inline(&paragraph)).trim().to_string());
+    }
+    out.retain(|block| !block.is_empty());
+    out.join("\n\n")
+}
+
+
+/// Render page content as Markdown.
+///
+/// Blocks are separated by blank lines, `h3` is rendered as `##` and `h4` as `###`,
+/// so the result is converted back by `from_markdown` to the same tags.
+/// Elements without Markdown syntax (`aside`, `u`, `iframe`, `video`)
+/// are rendered as HTML.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::{build_content, content::to_markdown};
+/// let content = build_content(r#"[
+///     {"tag": "h3", "children": ["Title"]},
+///     {"tag": "p", "children": ["Some ", {"tag": "b", "children": ["bold"]}, " text"]}
+/// ]"#).unwrap();
+/// assert_eq!(to_markdown(&content), "## Title\n\nSome **bold** text\n");
+/// ```
+pub fn to_markdown(content: &[Node]) -> String {
+    let mut markdown = blocks(content);
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+    markdown
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+    use crate::content::from_markdown;
+
+    use super::to_markdown;
+
+    #[test]
+    fn nodes_to_markdown() {
+        let content = build_content(r#"[
+            {"tag": "h4", "children": ["Subtitle"]},
+            {"tag": "p", "children": [
+                "1 * 2 ",
+                {"tag": "em", "children": ["em"]},
+                " ",
+                {"tag": "code", "children": ["a`b"]},
+                " ",
+                {"tag": "a", "attrs": {"href": "https://telegra.ph"}, "children": ["link"]}
+            ]},
+            {"tag": "ol", "children": [
+                {"tag": "li", "children": ["One"]},
+                {"tag": "li", "children": ["Two", {"tag": "ul", "children": [{"tag": "li", "children": ["Nested"]}]}]}
+            ]},
+            {"tag": "aside", "children": ["Pull quote"]}
+        ]"#).unwrap();
+        assert_eq!(to_markdown(&content), concat!(
+            "### Subtitle\n\n",
+            "1 \\* 2 *em* ``a`b`` [link](https://telegra.ph)\n\n",
+            "1. One\n2. Two\n   - Nested\n\n",
+            "<aside>Pull quote</aside>\n"
+        ));
+    }
+
+    #[test]
+    fn markdown_round_trip() {
+        let content = build_content(r#"[
+            {"tag": "h3", "children": ["Title"]},
+            {"tag": "p", "children": ["Text with ", {"tag": "strong", "children": ["strong"]}, " and ", {"tag": "s", "children": ["s"]}]},
+            {"tag": "blockquote", "children": ["Quote"]},
+            {"tag": "p", "children": ["a &amp; b"]},
+            {"tag": "pre", "children": ["let a = 1;\n```"]},
+            {"tag": "figure", "children": [
+                {"tag": "img", "attrs": {"src": "/file/1.jpg"}},
+                {"tag": "figcaption", "children": ["Caption"]}
+            ]},
+            {"tag": "ul", "children": [{"tag": "li", "children": ["- not a list"]}]},
+            {"tag": "hr"},
+            {"tag": "video", "attrs": {"src": "/file/1.mp4"}},
+            {"tag": "aside", "children": ["Pull quote"]},
+            {"tag": "iframe", "attrs": {"src": "/embed/youtube?url=x"}}
+        ]"#).unwrap();
+        assert_eq!(from_markdown(&to_markdown(&content)).unwrap(), content);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::types::{Node, NodeTag};
+
+
+/// `true` if `src` is not a url or a file hosted by Telegraph.
+fn is_local(src: &str) -> bool {
+    !src.is_empty()
+        && !src.contains("://")
+        && !src.starts_with("//")
+        && !src.starts_with("data:")
+        && !src.starts_with("/file/")
+        && !src.starts_with("/embed/")
+}
+
+
+fn collect_media(nodes: &[Node], sources: &mut Vec<String>) {
+    for node in nodes {
+        if let Node::NodeElement(element) = node {
+            if matches!(element.tag, Some(NodeTag::Img | NodeTag::Video)) {
+                if let Some(src) = element.attrs.as_ref().and_then(|attrs| attrs.src()) {
+                    if is_local(src) && !sources.iter().any(|source| source == src) {
+                        sources.push(src.into());
+                    }
+                }
+            }
+            collect_media(element.children.as_deref().unwrap_or_default(), sources);
+        }
+    }
+}
+
+
+fn visit_media(nodes: &mut [Node], visit: &mut dyn FnMut(&mut String)) {
+    for node in nodes {
+        if let Node::NodeElement(element) = node {
+            if matches!(element.tag, Some(NodeTag::Img | NodeTag::Video)) {
+                if let Some(attrs) = element.attrs.as_mut() {
+                    if let Some(mut src) = attrs.src().map(String::from) {
+                        visit(&mut src);
+                        attrs.insert("src", &src);
+                    }
+                }
+            }
+            if let Some(children) = element.children.as_mut() {
+                visit_media(children, visit);
+            }
+        }
+    }
+}
+
+
+/// Sources of `img` and `video` elements which refer to local files,
+/// in document order without duplicates.
+///
+/// Urls, `data:` uris and files already hosted by Telegraph (`/file/...`) are skipped.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::{build_content, content::local_media};
+/// let content = build_content(r#"[
+///     {"tag": "img", "attrs": {"src": "./figures/a.png"}},
+///     {"tag": "img", "attrs": {"src": "https://telegra.ph/file/b.png"}}
+/// ]"#).unwrap();
+/// assert_eq!(local_media(&content), vec!["./figures/a.png"]);
+/// ```
+pub fn local_media(content: &[Node]) -> Vec<String> {
+    let mut sources = vec![];
+    collect_media(content, &mut sources);
+    sources
+}
+
+
+/// Replaces the sources of `img` and `video` elements for which `replace` returns a value.
+pub fn rewrite_media<F>(content: &mut [Node], mut replace: F)
+where F: FnMut(&str) -> Option<String>
+{
+    visit_media(content, &mut |src| {
+        if let Some(new_src) = replace(src) {
+            *src = new_src;
+        }
+    });
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+
+    use super::{local_media, rewrite_media};
+
+    #[test]
+    fn local_media_sources() {
+        let mut content = build_content(r#"[
+            {"tag": "figure", "children": [{"tag": "img", "attrs": {"src": "figures/a.png"}}]},
+            {"tag": "p", "children": [{"tag": "img", "attrs": {"src": "/file/b.png"}}]},
+            {"tag": "video", "attrs": {"src": "clip.mp4"}},
+            {"tag": "img", "attrs": {"src": "figures/a.png"}},
+            {"tag": "iframe", "attrs": {"src": "local.html"}}
+        ]"#).unwrap();
+        assert_eq!(local_media(&content), vec!["figures/a.png", "clip.mp4"]);
+
+        rewrite_media(&mut content, |src| match src {
+            "figures/a.png" => Some("/file/1.png".into()),
+            _ => None
+        });
+        let expected = build_content(r#"[
+            {"tag": "figure", "children": [{"tag": "img", "attrs": {"src": "/file/1.png"}}]},
+            {"tag": "p", "children": [{"tag": "img", "attrs": {"src": "/file/b.png"}}]},
+            {"tag": "video", "attrs": {"src": "clip.mp4"}},
+            {"tag": "img", "attrs": {"src": "/file/1.png"}},
+            {"tag": "iframe", "attrs": {"src": "local.html"}}
+        ]"#).unwrap();
+        assert_eq!(content, expected);
+    }
+}
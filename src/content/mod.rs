@@ -0,0 +1,45 @@
+//! Conversion of page content
+//!
+//! Helpers to build [`Node`][crate::types::Node] trees from other formats
+//! instead of the raw Telegraph JSON accepted by [`build_content`][crate::build_content],
+//! and to render page content back to them.
+
+mod diff;
+mod html;
+mod markdown;
+mod media;
+mod split;
+mod text;
+mod validate;
+
+pub use diff::{diff, Change, ChangeKind, ContentDiff};
+pub use html::{from_html, from_html_strict, to_html, write_html};
+#[cfg(any(test, feature = "markdown"))]
+pub use markdown::from_markdown;
+pub use markdown::to_markdown;
+pub use media::{local_media, rewrite_media};
+pub use split::split_content;
+pub use text::{description, stats, to_plain_text, ContentStats, MAX_DESCRIPTION_LENGTH, WORDS_PER_MINUTE};
+pub use validate::{validate, ContentIssue, ContentIssueKind, MAX_CONTENT_SIZE};
+
+use crate::types::{Node, NodeElement, NodeElementAttrs, NodeTag};
+
+
+/// Appends `node` to `children`, merging adjacent text nodes.
+fn push_node(children: &mut Vec<Node>, node: Node) {
+    if let (Some(Node::String(last)), Node::String(text)) = (children.last_mut(), &node) {
+        last.push_str(text);
+    } else {
+        children.push(node);
+    }
+}
+
+
+/// Element node, `children` are omitted when empty.
+fn element(tag: NodeTag, attrs: Option<NodeElementAttrs>, children: Vec<Node>) -> Node {
+    Node::NodeElement(NodeElement {
+        tag: Some(tag),
+        attrs,
+        children: if children.is_empty() { None } else { Some(children) }
+    })
+}
@@ -0,0 +1,92 @@
+use crate::content::{ContentIssue, ContentIssueKind};
+use crate::types::{Node, NodeTag};
+use crate::TelegraphError;
+
+
+fn size(node: &Node) -> usize {
+    serde_json::to_string(node).map_or(0, |json| json.len())
+}
+
+
+fn is_heading(node: &Node) -> bool {
+    matches!(node, Node::NodeElement(element) if matches!(element.tag, Some(NodeTag::H3 | NodeTag::H4)))
+}
+
+
+/// Splits the content between top-level nodes into parts
+/// whose serialized size is at most `max_size` bytes.
+///
+/// Blocks are never cut, so paragraphs, code blocks and lists stay whole,
+/// and a heading ending a part is moved to the next one.
+/// A top-level node bigger than `max_size` is reported as
+/// [`ContentIssueKind::TooBig`] in [`TelegraphError::InvalidContent`].
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::{content::split_content, types::Node};
+/// let content: Vec<Node> = (0..3).map(|_| Node::p().text(&"a".repeat(100)).into()).collect();
+/// let parts = split_content(content, 300).unwrap();
+/// assert_eq!(parts.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 1]);
+/// ```
+pub fn split_content(content: Vec<Node>, max_size: usize) -> Result<Vec<Vec<Node>>, TelegraphError> {
+    let mut parts = vec![];
+    let mut part: Vec<Node> = vec![];
+    // Size of the serialized part: brackets and the commas between nodes
+    let mut part_size = 2;
+    for (index, node) in content.into_iter().enumerate() {
+        let node_size = size(&node);
+        if node_size + 2 > max_size {
+            let issue = ContentIssue { path: vec![index], kind: ContentIssueKind::TooBig { size: node_size } };
+            return Err(TelegraphError::InvalidContent(vec![issue]));
+        }
+        let separator = usize::from(!part.is_empty());
+        if part_size + separator + node_size > max_size {
+            let mut next = vec![];
+            if part.len() > 1 && part.last().is_some_and(is_heading) {
+                next.extend(part.pop());
+            }
+            parts.push(part);
+            part = next;
+            part_size = 2 + part.iter().map(size).sum::<usize>();
+        }
+        part_size += usize::from(!part.is_empty()) + node_size;
+        part.push(node);
+    }
+    if !part.is_empty() {
+        parts.push(part);
+    }
+    Ok(parts)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+    use crate::content::ContentIssueKind;
+    use crate::types::Node;
+    use crate::TelegraphError;
+
+    use super::split_content;
+
+    #[test]
+    fn split_blocks() {
+        let content = build_content(&format!(r#"[
+            {{"tag": "p", "children": ["{}"]}},
+            {{"tag": "h3", "children": ["Section"]}},
+            {{"tag": "ul", "children": [{{"tag": "li", "children": ["One"]}}, {{"tag": "li", "children": ["Two"]}}]}},
+            {{"tag": "pre", "children": ["code"]}}
+        ]"#, "text ".repeat(40))).unwrap();
+        let max_size = serde_json::to_string(&content[..2]).unwrap().len();
+        let parts = split_content(content.clone(), max_size).unwrap();
+        assert_eq!(parts, vec![content[..1].to_vec(), content[1..].to_vec()]);
+        for part in &parts {
+            assert!(serde_json::to_string(part).unwrap().len() <= max_size);
+        }
+        assert_eq!(split_content(content.clone(), 64 * 1024).unwrap(), vec![content]);
+        assert!(split_content(vec![], 100).unwrap().is_empty());
+
+        let big: Vec<Node> = vec![Node::p().text(&"a".repeat(200)).into()];
+        let error = split_content(big, 100).unwrap_err();
+        assert!(matches!(error, TelegraphError::InvalidContent(issues) if matches!(issues[0].kind, ContentIssueKind::TooBig { .. })));
+    }
+}
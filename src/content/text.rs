@@ -0,0 +1,256 @@
+use std::time::Duration;
+
+use crate::types::{Node, NodeElement, NodeTag};
+
+
+/// Maximum length of a description in characters
+pub const MAX_DESCRIPTION_LENGTH: usize = 150;
+
+/// Reading speed used by [`ContentStats::reading_time`]
+pub const WORDS_PER_MINUTE: usize = 200;
+
+/// Time to look at an image added by [`ContentStats::reading_time`]
+const SECONDS_PER_IMAGE: u64 = 12;
+
+
+/// Statistics of page content, see [`stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentStats {
+    /// Number of whitespace separated words of the plain text, without list markers
+    pub words: usize,
+    /// Number of characters of the plain text, without list markers
+    pub characters: usize,
+    /// Number of `img` elements
+    pub images: usize,
+    /// Number of `video` elements and YouTube or Vimeo embeds
+    pub videos: usize,
+    /// Estimated reading time: the words at [`WORDS_PER_MINUTE`]
+    /// and 12 seconds per image, rounded up to a second
+    pub reading_time: Duration
+}
+
+
+/// Plain text with separators between blocks.
+struct TextWriter {
+    text: String,
+    /// Line breaks to write before the next text
+    breaks: usize,
+    /// Whether list items start with a marker
+    markers: bool
+}
+
+
+impl TextWriter {
+    fn write(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if !self.text.is_empty() {
+            self.text.push_str(&"\n".repeat(self.breaks));
+        }
+        self.breaks = 0;
+        self.text.push_str(text);
+    }
+
+    fn line_break(&mut self, breaks: usize) {
+        self.breaks = self.breaks.max(breaks);
+    }
+
+    fn nodes(&mut self, nodes: &[Node], list: Option<NodeTag>) {
+        let mut number = 0;
+        for node in nodes {
+            match node {
+                Node::String(text) => self.write(text),
+                Node::NodeElement(element) => {
+                    if element.tag == Some(NodeTag::Li) {
+                        number += 1;
+                    }
+                    self.element(element, list, number);
+                }
+            }
+        }
+    }
+
+    fn element(&mut self, element: &NodeElement, list: Option<NodeTag>, number: usize) {
+        let children = element.children.as_deref().unwrap_or_default();
+        match element.tag {
+            Some(NodeTag::Br) => self.line_break(1),
+            Some(NodeTag::Li) => {
+                self.line_break(1);
+                match list {
+                    _ if !self.markers => {},
+                    Some(NodeTag::Ol) => self.write(&format!("{}. ", number)),
+                    _ => self.write("- ")
+                }
+                self.nodes(children, None);
+                self.line_break(1);
+            },
+            Some(NodeTag::Ul | NodeTag::Ol) => {
+                self.line_break(2);
+                self.nodes(children, element.tag);
+                self.line_break(2);
+            },
+            Some(NodeTag::Figcaption) => {
+                self.line_break(1);
+                self.nodes(children, None);
+                self.line_break(1);
+            },
+            Some(
+                NodeTag::P | NodeTag::H3 | NodeTag::H4 | NodeTag::Blockquote | NodeTag::Aside
+                | NodeTag::Figure | NodeTag::Pre | NodeTag::Hr | NodeTag::Img | NodeTag::Video | NodeTag::Iframe
+            ) => {
+                self.line_break(2);
+                self.nodes(children, None);
+                self.line_break(2);
+            },
+            _ => self.nodes(children, None)
+        }
+    }
+}
+
+
+/// Text of the content without markup.
+///
+/// Paragraphs, headings, quotes, code blocks, figures and lists are separated
+/// by an empty line, list items and `br` by a line break.
+/// List items start with `- `, or with their number in ordered lists.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::{content::{from_html, to_plain_text}};
+/// let content = from_html("<h3>Title</h3><p>Some <b>bold</b> text</p><ol><li>One</li><li>Two</li></ol>").unwrap();
+/// assert_eq!(to_plain_text(&content), "Title\n\nSome bold text\n\n1. One\n2. Two");
+/// ```
+pub fn to_plain_text(content: &[Node]) -> String {
+    write_text(content, true)
+}
+
+
+fn write_text(content: &[Node], markers: bool) -> String {
+    let mut writer = TextWriter { text: String::new(), breaks: 0, markers };
+    writer.nodes(content, None);
+    writer.text
+}
+
+
+fn count_media(nodes: &[Node], stats: &mut ContentStats) {
+    for node in nodes {
+        if let Node::NodeElement(element) = node {
+            let src = element.attrs.as_ref().and_then(|attrs| attrs.src()).unwrap_or_default();
+            match element.tag {
+                Some(NodeTag::Img) => stats.images += 1,
+                Some(NodeTag::Video) => stats.videos += 1,
+                Some(NodeTag::Iframe) if src.starts_with("/embed/youtube") || src.starts_with("/embed/vimeo") => stats.videos += 1,
+                _ => {}
+            }
+            count_media(element.children.as_deref().unwrap_or_default(), stats);
+        }
+    }
+}
+
+
+/// Word, character and media counts and the reading time of the content.
+///
+/// # Example
+/// ```rust
+/// # use std::time::Duration;
+/// # use telegraph_api_rs::content::{from_html, stats};
+/// let content = from_html(r#"<p>Three short words</p><figure><img src="/file/a.png"></figure>"#).unwrap();
+/// let stats = stats(&content);
+/// assert_eq!((stats.words, stats.images), (3, 1));
+/// assert_eq!(stats.reading_time, Duration::from_secs(13));
+/// ```
+pub fn stats(content: &[Node]) -> ContentStats {
+    let text = write_text(content, false);
+    let mut stats = ContentStats {
+        words: text.split_whitespace().count(),
+        characters: text.chars().count(),
+        ..ContentStats::default()
+    };
+    count_media(content, &mut stats);
+    let reading = (stats.words * 60).div_ceil(WORDS_PER_MINUTE) as u64;
+    stats.reading_time = Duration::from_secs(reading + stats.images as u64 * SECONDS_PER_IMAGE);
+    stats
+}
+
+
+fn first_paragraph(nodes: &[Node]) -> Option<String> {
+    nodes.iter().find_map(|node| match node {
+        Node::NodeElement(element) if element.tag == Some(NodeTag::P) => {
+            let text = to_plain_text(element.children.as_deref().unwrap_or_default());
+            Some(text).filter(|text| !text.trim().is_empty())
+        },
+        Node::NodeElement(element) => first_paragraph(element.children.as_deref().unwrap_or_default()),
+        Node::String(_) => None
+    })
+}
+
+
+/// Description of the page like the one Telegraph returns in [`Page`][crate::types::Page]:
+/// the text of the first non-empty paragraph, or of the whole content without paragraphs,
+/// with collapsed whitespace, cut to [`MAX_DESCRIPTION_LENGTH`] characters at a word boundary.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::content::{from_html, description};
+/// let content = from_html("<h3>Title</h3><p></p><p>First\nparagraph</p><p>Second</p>").unwrap();
+/// assert_eq!(description(&content), "First paragraph");
+/// ```
+pub fn description(content: &[Node]) -> String {
+    let text = first_paragraph(content).unwrap_or_else(|| to_plain_text(content));
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut description = String::new();
+    for word in words {
+        let separator = usize::from(!description.is_empty());
+        if description.chars().count() + separator + word.chars().count() > MAX_DESCRIPTION_LENGTH {
+            if description.is_empty() {
+                description = word.chars().take(MAX_DESCRIPTION_LENGTH).collect();
+            }
+            break;
+        }
+        if separator == 1 {
+            description.push(' ');
+        }
+        description.push_str(word);
+    }
+    description
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::build_content;
+
+    use super::{description, stats, to_plain_text, MAX_DESCRIPTION_LENGTH};
+
+    #[test]
+    fn plain_text() {
+        let content = build_content(r#"[
+            {"tag": "h3", "children": ["Title"]},
+            {"tag": "p", "children": ["Line", {"tag": "br"}, "next ", {"tag": "a", "attrs": {"href": "/a"}, "children": ["link"]}]},
+            {"tag": "figure", "children": [
+                {"tag": "img", "attrs": {"src": "/file/a.png"}},
+                {"tag": "figcaption", "children": ["Caption"]}
+            ]},
+            {"tag": "ul", "children": [{"tag": "li", "children": ["One"]}, {"tag": "li", "children": ["Two"]}]},
+            {"tag": "hr"},
+            {"tag": "pre", "children": ["fn main() {\n}"]},
+            {"tag": "figure", "children": [{"tag": "iframe", "attrs": {"src": "/embed/youtube?url=x"}}]}
+        ]"#).unwrap();
+        let text = to_plain_text(&content);
+        assert_eq!(text, "Title\n\nLine\nnext link\n\nCaption\n\n- One\n- Two\n\nfn main() {\n}");
+
+        let stats = stats(&content);
+        assert_eq!((stats.words, stats.characters), (11, text.chars().count() - 4));
+        assert_eq!((stats.images, stats.videos), (1, 1));
+        assert_eq!(stats.reading_time, Duration::from_secs(4 + 12));
+
+        assert_eq!(description(&content), "Line next link");
+        let long = build_content(&format!(r#"[{{"tag": "p", "children": ["{}"]}}]"#, "word ".repeat(40))).unwrap();
+        let long_description = description(&long);
+        assert!(long_description.len() <= MAX_DESCRIPTION_LENGTH && long_description.ends_with("word"));
+        assert_eq!(description(&build_content(r#"[{"tag": "h3", "children": ["Only title"]}]"#).unwrap()), "Only title");
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+
+use crate::types::{Node, NodeElement, NodeTag};
+
+
+/// Maximum size of the serialized page content accepted by Telegraph
+pub const MAX_CONTENT_SIZE: usize = 64 * 1024;
+
+
+/// Problem found in page content by [`validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentIssue {
+    /// Indices of the node in the content tree,
+    /// empty for issues of the whole content.
+    pub path: Vec<usize>,
+    /// Kind of the problem
+    pub kind: ContentIssueKind
+}
+
+
+/// Kind of [`ContentIssue`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentIssueKind {
+    /// Content has no nodes
+    Empty,
+    /// Serialized content exceeds [`MAX_CONTENT_SIZE`]
+    TooBig {
+        /// Size of the serialized content in bytes
+        size: usize
+    },
+    /// Element has no tag
+    MissingTag,
+    /// Element has an attribute which Telegraph does not support for its tag
+    UnsupportedAttr {
+        /// Tag of the element
+        tag: NodeTag,
+        /// Name of the attribute
+        attr: String
+    },
+    /// Element can not be placed inside its parent
+    IllegalNesting {
+        /// Tag of the parent, `None` for the top level
+        parent: Option<NodeTag>,
+        /// Tag of the element
+        child: NodeTag
+    },
+    /// Text can not be placed inside its parent
+    IllegalText {
+        /// Tag of the parent
+        parent: NodeTag
+    },
+    /// Element which can not have children has them
+    VoidWithChildren(NodeTag)
+}
+
+
+impl fmt::Display for ContentIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{:?}: ", self.path)?;
+        }
+        match &self.kind {
+            ContentIssueKind::Empty => write!(f, "content is empty"),
+            ContentIssueKind::TooBig { size } => write!(f, "content size {} exceeds {} bytes", size, MAX_CONTENT_SIZE),
+            ContentIssueKind::MissingTag => write!(f, "element has no tag"),
+            ContentIssueKind::UnsupportedAttr { tag, attr } => write!(f, "attribute `{}` is not allowed on `{}`", attr, tag.as_str()),
+            ContentIssueKind::IllegalNesting { parent: Some(parent), child } => write!(f, "`{}` is not allowed inside `{}`", child.as_str(), parent.as_str()),
+            ContentIssueKind::IllegalNesting { parent: None, child } => write!(f, "`{}` is not allowed at the top level", child.as_str()),
+            ContentIssueKind::IllegalText { parent } => write!(f, "text is not allowed inside `{}`", parent.as_str()),
+            ContentIssueKind::VoidWithChildren(tag) => write!(f, "`{}` can not have children", tag.as_str())
+        }
+    }
+}
+
+
+fn is_inline(tag: NodeTag) -> bool {
+    matches!(tag,
+        NodeTag::A | NodeTag::B | NodeTag::Br | NodeTag::Code | NodeTag::Em |
+        NodeTag::I | NodeTag::Img | NodeTag::S | NodeTag::Strong | NodeTag::U
+    )
+}
+
+
+/// Whether Telegraph accepts the attribute on the tag.
+pub(super) fn allowed_attr(tag: NodeTag, name: &str) -> bool {
+    match name {
+        "href" => tag == NodeTag::A,
+        "src" => matches!(tag, NodeTag::Img | NodeTag::Iframe | NodeTag::Video),
+        "id" => matches!(tag, NodeTag::H3 | NodeTag::H4),
+        _ => false
+    }
+}
+
+
+fn allowed_child(parent: Option<NodeTag>, ancestors: &[NodeTag], child: NodeTag) -> bool {
+    let parent = match parent {
+        Some(parent) => parent,
+        None => return !matches!(child, NodeTag::Li | NodeTag::Figcaption)
+    };
+    match (parent, child) {
+        (NodeTag::A, NodeTag::A) => false,
+        (NodeTag::Ul | NodeTag::Ol, child) => child == NodeTag::Li,
+        (_, NodeTag::Li) => false,
+        (NodeTag::Figure, child) => matches!(child, NodeTag::Img | NodeTag::Iframe | NodeTag::Video | NodeTag::Figcaption),
+        (_, NodeTag::Figcaption) => false,
+        (_, NodeTag::A) => !ancestors.contains(&NodeTag::A),
+        (NodeTag::Li, NodeTag::Ul | NodeTag::Ol) => true,
+        (_, child) => is_inline(child)
+    }
+}
+
+
+fn allowed_text(parent: NodeTag) -> bool {
+    !matches!(parent, NodeTag::Ul | NodeTag::Ol | NodeTag::Figure)
+}
+
+
+fn check_nodes(
+    nodes: &[Node],
+    parent: Option<NodeTag>,
+    ancestors: &mut Vec<NodeTag>,
+    path: &mut Vec<usize>,
+    issues: &mut Vec<ContentIssue>
+) {
+    for (index, node) in nodes.iter().enumerate() {
+        path.push(index);
+        match node {
+            Node::String(text) => {
+                if let Some(parent) = parent {
+                    if !allowed_text(parent) && !text.trim().is_empty() {
+                        issues.push(ContentIssue { path: path.clone(), kind: ContentIssueKind::IllegalText { parent } });
+                    }
+                }
+            },
+            Node::NodeElement(element) => check_element(element, parent, ancestors, path, issues)
+        }
+        path.pop();
+    }
+}
+
+
+fn check_element(
+    element: &NodeElement,
+    parent: Option<NodeTag>,
+    ancestors: &mut Vec<NodeTag>,
+    path: &mut Vec<usize>,
+    issues: &mut Vec<ContentIssue>
+) {
+    let mut issue = |kind| issues.push(ContentIssue { path: path.clone(), kind });
+    let tag = match element.tag {
+        Some(tag) => tag,
+        None => {
+            issue(ContentIssueKind::MissingTag);
+            return;
+        }
+    };
+
+    for (name, _) in element.attrs.iter().flat_map(|attrs| attrs.iter()) {
+        if !allowed_attr(tag, name) {
+            issue(ContentIssueKind::UnsupportedAttr { tag, attr: name.into() });
+        }
+    }
+    if !allowed_child(parent, ancestors, tag) {
+        issue(ContentIssueKind::IllegalNesting { parent, child: tag });
+    }
+
+    let children = element.children.as_deref().unwrap_or_default();
+    if tag.is_void() && !children.is_empty() {
+        issue(ContentIssueKind::VoidWithChildren(tag));
+        return;
+    }
+
+    ancestors.push(tag);
+    check_nodes(children, Some(tag), ancestors, path, issues);
+    ancestors.pop();
+}
+
+
+/// Checks page content against the Telegraph rules before sending it.
+///
+/// Reports empty content, content over [`MAX_CONTENT_SIZE`] bytes,
+/// elements without tag, attributes not allowed for the tag
+/// (`href` on `a`, `src` on `img`, `iframe` and `video`, `id` on `h3` and `h4`)
+/// and illegal nesting, such as blocks inside inline elements
+/// or `li` outside of lists.
+///
+/// # Example
+/// ```rust
+/// # use telegraph_api_rs::{build_content, content::validate};
+/// let content = build_content(r#"[{"tag": "p", "children": [{"tag": "p"}]}]"#).unwrap();
+/// let issues = validate(&content).unwrap_err();
+/// assert_eq!(issues[0].to_string(), "[0, 0]: `p` is not allowed inside `p`");
+/// ```
+pub fn validate(content: &[Node]) -> Result<(), Vec<ContentIssue>> {
+    let mut issues = vec![];
+    if content.is_empty() {
+        issues.push(ContentIssue { path: vec![], kind: ContentIssueKind::Empty });
+    }
+    let size = serde_json::to_string(content).map_or(0, |json| json.len());
+    if size > MAX_CONTENT_SIZE {
+        issues.push(ContentIssue { path: vec![], kind: ContentIssueKind::TooBig { size } });
+    }
+    check_nodes(content, None, &mut vec![], &mut vec![], &mut issues);
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{build_content, Telegraph, TelegraphError};
+    use crate::types::{Node, NodeTag};
+
+    use super::{validate, ContentIssue, ContentIssueKind, MAX_CONTENT_SIZE};
+
+    #[test]
+    fn valid_content() {
+        let content = build_content(r#"[
+            {"tag": "h3", "attrs": {"id": "Title"}, "children": ["Title"]},
+            {"tag": "p", "children": ["Text ", {"tag": "a", "attrs": {"href": "/"}, "children": [{"tag": "b", "children": ["link"]}]}]},
+            {"tag": "ul", "children": [{"tag": "li", "children": ["One", {"tag": "ol", "children": [{"tag": "li", "children": ["Two"]}]}]}]},
+            {"tag": "figure", "children": [{"tag": "img", "attrs": {"src": "/file/1.jpg"}}, {"tag": "figcaption", "children": ["Caption"]}]}
+        ]"#).unwrap();
+        assert_eq!(validate(&content), Ok(()));
+    }
+
+    #[test]
+    fn invalid_content() {
+        let content = build_content(r#"[
+            {"tag": "p", "attrs": {"src": "/file/1.jpg", "class": "lead"}, "children": [{"tag": "blockquote"}]},
+            {"tag": "li", "children": ["Item"]},
+            {"tag": "ul", "children": ["Text"]},
+            {"tag": "img", "attrs": {"src": "/file/1.jpg"}, "children": ["Text"]},
+            {"children": ["No tag"]},
+            {"tag": "ul", "children": [{"tag": "a", "attrs": {"href": "/"}, "children": ["Link"]}]},
+            {"tag": "ol", "children": [{"tag": "a", "attrs": {"href": "/"}}]},
+            {"tag": "figure", "children": [{"tag": "a", "attrs": {"href": "/"}, "children": [{"tag": "img", "attrs": {"src": "/file/1.jpg"}}]}]}
+        ]"#).unwrap();
+        let issues = validate(&content).unwrap_err();
+        assert_eq!(issues, vec![
+            ContentIssue { path: vec![0], kind: ContentIssueKind::UnsupportedAttr { tag: NodeTag::P, attr: "class".into() } },
+            ContentIssue { path: vec![0], kind: ContentIssueKind::UnsupportedAttr { tag: NodeTag::P, attr: "src".into() } },
+            ContentIssue { path: vec![0, 0], kind: ContentIssueKind::IllegalNesting { parent: Some(NodeTag::P), child: NodeTag::Blockquote } },
+            ContentIssue { path: vec![1], kind: ContentIssueKind::IllegalNesting { parent: None, child: NodeTag::Li } },
+            ContentIssue { path: vec![2, 0], kind: ContentIssueKind::IllegalText { parent: NodeTag::Ul } },
+            ContentIssue { path: vec![3], kind: ContentIssueKind::VoidWithChildren(NodeTag::Img) },
+            ContentIssue { path: vec![4], kind: ContentIssueKind::MissingTag },
+            ContentIssue { path: vec![5, 0], kind: ContentIssueKind::IllegalNesting { parent: Some(NodeTag::Ul), child: NodeTag::A } },
+            ContentIssue { path: vec![6, 0], kind: ContentIssueKind::IllegalNesting { parent: Some(NodeTag::Ol), child: NodeTag::A } },
+            ContentIssue { path: vec![7, 0], kind: ContentIssueKind::IllegalNesting { parent: Some(NodeTag::Figure), child: NodeTag::A } }
+        ]);
+    }
+
+    #[test]
+    fn content_too_big() {
+        let content: Vec<Node> = vec![Node::p().text(&"a".repeat(MAX_CONTENT_SIZE)).into()];
+        let issues = validate(&content).unwrap_err();
+        assert!(matches!(issues[0].kind, ContentIssueKind::TooBig { size } if size > MAX_CONTENT_SIZE));
+        assert_eq!(validate(&[]).unwrap_err()[0].kind, ContentIssueKind::Empty);
+    }
+
+    #[test]
+    fn validate_before_send() {
+        let result = Telegraph::new().create_page()
+            .access_token("token")
+            .title("Title")
+            .content(vec![Node::li().text("Item").into()])
+            .validate(true)
+            .send();
+        match result {
+            Err(TelegraphError::InvalidContent(issues)) => assert_eq!(issues.len(), 1),
+            _ => panic!("content must be rejected before sending")
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! Mirroring a directory of Markdown files to Telegraph pages
+//!
+//! [`DirSync`] creates a page for every new `.md` file of a directory
+//! and edits the pages of the files changed since the previous run.
+//! The page of every file and the hash of its content are kept in a [`Manifest`]
+//! saved in the directory, so running the sync again without changes sends no requests.
+//!
+//! The title of a page is the first `# heading` of its file, removed from the content,
+//! or the file name without the extension.
+//! Pages of deleted files are left untouched and keep their manifest entries,
+//! so a restored file edits its previous page.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::content::from_markdown;
+use crate::store::write_atomic;
+use crate::types::Page;
+use crate::{Telegraph, TelegraphError};
+
+
+/// Default name of the manifest file in the synced directory
+pub const MANIFEST_FILE: &str = ".telegraph-sync.json";
+
+
+/// Page of a synced file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Path of the page
+    pub path: String,
+    /// URL of the page
+    pub url: String,
+    /// Hash of the file content published to the page
+    pub hash: String
+}
+
+
+/// Pages of the synced files, keyed by the file path relative to the directory
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    files: BTreeMap<String, ManifestEntry>
+}
+
+
+impl Manifest {
+    /// Loads the manifest saved at `path`, empty if the file does not exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, TelegraphError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(error) => Err(error.into())
+        }
+    }
+
+    /// Page of the file.
+    pub fn get(&self, file: &str) -> Option<&ManifestEntry> {
+        self.files.get(file)
+    }
+
+    /// Files and their pages, ordered by file.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ManifestEntry)> {
+        self.files.iter().map(|(file, entry)| (file.as_str(), entry))
+    }
+
+    fn save(&self, path: &Path) -> Result<(), TelegraphError> {
+        Ok(write_atomic(path, serde_json::to_string_pretty(self)?.as_bytes())?)
+    }
+}
+
+
+/// What [`DirSync`] did with a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncAction {
+    /// New file, the page was created
+    Created,
+    /// Changed file, the page was edited
+    Updated,
+    /// File is unchanged since the previous run
+    Unchanged
+}
+
+
+impl fmt::Display for SyncAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncAction::Created => write!(f, "created"),
+            SyncAction::Updated => write!(f, "updated"),
+            SyncAction::Unchanged => write!(f, "unchanged")
+        }
+    }
+}
+
+
+/// Synced file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncedFile {
+    /// File path relative to the directory
+    pub file: String,
+    /// Path of the page
+    pub path: String,
+    /// What was done
+    pub action: SyncAction
+}
+
+
+/// Files synced by [`DirSync::run`], ordered by file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SyncReport {
+    /// Every Markdown file of the directory
+    pub files: Vec<SyncedFile>
+}
+
+
+impl SyncReport {
+    /// Number of files with the action.
+    pub fn count(&self, action: SyncAction) -> usize {
+        self.files.iter().filter(|file| file.action == action).count()
+    }
+}
+
+
+/// Sync of a directory, see [`Telegraph::sync_dir`]
+#[derive(Clone)]
+pub struct DirSync {
+    telegraph: Telegraph,
+    access_token: String,
+    dir: PathBuf,
+    manifest: Option<PathBuf>,
+    author_name: Option<String>,
+    author_url: Option<String>
+}
+
+
+impl DirSync {
+    pub(crate) fn new(telegraph: Telegraph, access_token: &str, dir: &Path) -> Self {
+        DirSync {
+            telegraph,
+            access_token: access_token.into(),
+            dir: dir.into(),
+            manifest: None,
+            author_name: None,
+            author_url: None
+        }
+    }
+
+    /// Path of the manifest, [`MANIFEST_FILE`] in the directory by default.
+    pub fn manifest<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.manifest = Some(path.as_ref().into());
+        self
+    }
+
+    /// Author name of the pages.
+    pub fn author_name(mut self, author_name: &str) -> Self {
+        self.author_name = Some(author_name.into());
+        self
+    }
+
+    /// Profile link of the author of the pages.
+    pub fn author_url(mut self, author_url: &str) -> Self {
+        self.author_url = Some(author_url.into());
+        self
+    }
+
+    /// Path of the manifest file.
+    pub fn manifest_path(&self) -> PathBuf {
+        self.manifest.clone().unwrap_or_else(|| self.dir.join(MANIFEST_FILE))
+    }
+
+    /// Creates and edits the pages of the directory files.
+    ///
+    /// The manifest is saved after every created or edited page,
+    /// so a failed run resumes without duplicating pages.
+    pub fn run(&self) -> Result<SyncReport, TelegraphError> {
+        let manifest_path = self.manifest_path();
+        let mut manifest = Manifest::load(&manifest_path)?;
+        let mut files = vec![];
+        markdown_files(&self.dir, &self.dir, &mut files)?;
+        files.sort();
+
+        let mut report = SyncReport::default();
+        for file in files {
+            let text = fs::read_to_string(self.dir.join(&file))?;
+            let hash = hash(text.as_bytes());
+            let action = match manifest.get(&file) {
+                Some(entry) if entry.hash == hash => SyncAction::Unchanged,
+                Some(_) => SyncAction::Updated,
+                None => SyncAction::Created
+            };
+            if action != SyncAction::Unchanged {
+                let (title, markdown) = split_title(&text, &file);
+                let page = self.publish(manifest.get(&file), &title, markdown)?;
+                manifest.files.insert(file.clone(), ManifestEntry { path: page.path, url: page.url, hash });
+                manifest.save(&manifest_path)?;
+            }
+            report.files.push(SyncedFile { path: manifest.files[&file].path.clone(), file, action });
+        }
+        Ok(report)
+    }
+
+    fn publish(&self, entry: Option<&ManifestEntry>, title: &str, markdown: &str) -> Result<Page, TelegraphError> {
+        let content = from_markdown(markdown)?;
+        match entry {
+            Some(entry) => {
+                let mut request = self.telegraph.edit_page()
+                    .access_token(&self.access_token)
+                    .path(&entry.path)
+                    .title(title)
+                    .content(content);
+                if let Some(author_name) = &self.author_name {
+                    request = request.author_name(author_name);
+                }
+                if let Some(author_url) = &self.author_url {
+                    request = request.author_url(author_url);
+                }
+                request.send()
+            },
+            None => {
+                let mut request = self.telegraph.create_page()
+                    .access_token(&self.access_token)
+                    .title(title)
+                    .content(content);
+                if let Some(author_name) = &self.author_name {
+                    request = request.author_name(author_name);
+                }
+                if let Some(author_url) = &self.author_url {
+                    request = request.author_url(author_url);
+                }
+                request.send()
+            }
+        }
+    }
+}
+
+
+/// Relative paths of the Markdown files under `dir`, with `/` separators.
+fn markdown_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        // Symlinked directories are not followed, so links can not loop
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if !entry.file_name().to_string_lossy().starts_with('.') {
+                markdown_files(root, &path, files)?;
+            }
+        } else if (file_type.is_file() || path.is_file())
+            && matches!(path.extension().and_then(|extension| extension.to_str()), Some("md" | "markdown")) {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let parts: Vec<_> = relative.components().map(|part| part.as_os_str().to_string_lossy()).collect();
+            files.push(parts.join("/"));
+        }
+    }
+    Ok(())
+}
+
+
+/// Title from the first line `# Title` and the rest of the text,
+/// or the file name without the extension and the whole text.
+fn split_title<'a>(text: &'a str, file: &str) -> (String, &'a str) {
+    let trimmed = text.trim_start();
+    if let Some(heading) = trimmed.strip_prefix("# ") {
+        let (title, rest) = heading.split_once('\n').unwrap_or((heading, ""));
+        if !title.trim().is_empty() {
+            return (title.trim().into(), rest);
+        }
+    }
+    let name = file.rsplit('/').next().unwrap_or(file);
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    (stem.into(), text)
+}
+
+
+/// 64-bit FNV-1a, stable across runs and platforms.
+fn hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::testing::FakeTelegraph;
+
+    use super::{split_title, Manifest, SyncAction, MANIFEST_FILE};
+
+    #[test]
+    fn titles() {
+        assert_eq!(split_title("# Intro\n\nText", "docs/intro.md"), ("Intro".into(), "\nText"));
+        assert_eq!(split_title("Text", "docs/guide.md"), ("guide".into(), "Text"));
+        assert_eq!(split_title("## Part\nText", "part.md"), ("part".into(), "## Part\nText"));
+    }
+
+    #[test]
+    fn sync_directory() {
+        let dir = std::env::temp_dir().join(format!("telegraph-sync-{}", std::process::id()));
+        fs::create_dir_all(dir.join("guide")).unwrap();
+        fs::write(dir.join("index.md"), "# Welcome\n\nHello").unwrap();
+        fs::write(dir.join("guide/install.md"), "Run the installer").unwrap();
+        fs::write(dir.join("notes.txt"), "Not synced").unwrap();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(dir.join(".git/hidden.md"), "Not synced").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&dir, dir.join("guide/loop")).unwrap();
+
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let token = telegraph.create_account().short_name("Docs").send().unwrap().access_token.unwrap();
+        let sync = telegraph.sync_dir(&token, &dir);
+
+        let report = sync.run().unwrap();
+        let files: Vec<_> = report.files.iter().map(|file| (file.file.as_str(), file.action)).collect();
+        assert_eq!(files, vec![("guide/install.md", SyncAction::Created), ("index.md", SyncAction::Created)]);
+        let manifest = Manifest::load(dir.join(MANIFEST_FILE)).unwrap();
+        let index = manifest.get("index.md").unwrap().path.clone();
+        assert_eq!(telegraph.get_page().path(&index).send().unwrap().title, "Welcome");
+
+        assert_eq!(sync.run().unwrap().count(SyncAction::Unchanged), 2);
+
+        fs::write(dir.join("index.md"), "# Welcome\n\nHello again").unwrap();
+        fs::write(dir.join("faq.md"), "Questions").unwrap();
+        let report = sync.run().unwrap();
+        assert_eq!((report.count(SyncAction::Created), report.count(SyncAction::Updated)), (1, 1));
+        assert_eq!(report.files.iter().find(|file| file.file == "index.md").unwrap().path, index);
+        let page = telegraph.get_page().path(&index).return_content(true).send().unwrap();
+        assert_eq!(page.description, "Hello again");
+        assert_eq!(telegraph.get_page_list().access_token(&token).send().unwrap().total_count, 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -5,13 +5,155 @@ use serde::Deserialize;
 use serde_json;
 use std::{error, fmt, io};
 
+use crate::content::ContentIssue;
+use crate::requests::ViewsRangeIssue;
+use crate::series::IncompleteSeries;
+use crate::types::Account;
+#[cfg(feature = "upload")]
+use crate::upload::UploadIssue;
+
+
+macro_rules! api_error_kinds {
+    ($($(#[$doc:meta])* $variant:ident => $code:literal),* $(,)?) => {
+        /// Known Telegraph API error codes
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+        #[serde(from = "String")]
+        pub enum ApiErrorKind {
+            $($(#[$doc])* $variant,)*
+            /// Too many requests, retry after the number of seconds
+            FloodWait(u64),
+            /// Error code unknown to the library
+            Unknown(String)
+        }
+
+
+        impl ApiErrorKind {
+            /// Parses the error code returned by the API.
+            pub fn from_code(code: &str) -> Self {
+                match code {
+                    $($code => ApiErrorKind::$variant,)*
+                    _ => match code.strip_prefix("FLOOD_WAIT_").and_then(|seconds| seconds.parse().ok()) {
+                        Some(seconds) => ApiErrorKind::FloodWait(seconds),
+                        None => ApiErrorKind::Unknown(code.into())
+                    }
+                }
+            }
+
+            /// Error code as returned by the API.
+            pub fn code(&self) -> String {
+                match self {
+                    $(ApiErrorKind::$variant => $code.into(),)*
+                    ApiErrorKind::FloodWait(seconds) => format!("FLOOD_WAIT_{}", seconds),
+                    ApiErrorKind::Unknown(code) => code.clone()
+                }
+            }
+        }
+    };
+}
+
+
+api_error_kinds! {
+    /// Access token is missing or invalid
+    AccessTokenInvalid => "ACCESS_TOKEN_INVALID",
+    /// Account short name is missing
+    ShortNameRequired => "SHORT_NAME_REQUIRED",
+    /// Account short name is longer than 32 characters
+    ShortNameTooLong => "SHORT_NAME_TOO_LONG",
+    /// Author name is longer than 128 characters
+    AuthorNameTooLong => "AUTHOR_NAME_TOO_LONG",
+    /// Author url is longer than 512 characters
+    AuthorUrlTooLong => "AUTHOR_URL_TOO_LONG",
+    /// Page title is missing
+    TitleRequired => "TITLE_REQUIRED",
+    /// Page title is longer than 256 characters
+    TitleTooLong => "TITLE_TOO_LONG",
+    /// Page content is missing
+    ContentRequired => "CONTENT_REQUIRED",
+    /// Page content is bigger than 64 KB
+    ContentTooBig => "CONTENT_TOO_BIG",
+    /// Page content is not a valid array of nodes
+    ContentFormatInvalid => "CONTENT_FORMAT_INVALID",
+    /// Page content contains an unsupported tag
+    ContentTagInvalid => "CONTENT_TAG_INVALID",
+    /// Page path is missing
+    PathRequired => "PATH_REQUIRED",
+    /// Page does not exist
+    PageNotFound => "PAGE_NOT_FOUND",
+    /// Page belongs to another account
+    PageAccessDenied => "PAGE_ACCESS_DENIED",
+    /// Page was not saved, the request can be repeated
+    PageSaveFailed => "PAGE_SAVE_FAILED",
+    /// Account fields are not a valid array
+    FieldsFormatInvalid => "FIELDS_FORMAT_INVALID",
+    /// Offset of the page list is invalid
+    OffsetInvalid => "OFFSET_INVALID",
+    /// Limit of the page list is invalid
+    LimitInvalid => "LIMIT_INVALID",
+    /// Year of the views is invalid
+    YearInvalid => "YEAR_INVALID",
+    /// Month of the views is invalid
+    MonthInvalid => "MONTH_INVALID",
+    /// Day of the views is invalid
+    DayInvalid => "DAY_INVALID",
+    /// Hour of the views is invalid
+    HourInvalid => "HOUR_INVALID",
+    /// Date of the views is invalid
+    DateInvalid => "DATE_INVALID",
+    /// Request parameters are invalid
+    ParamsInvalid => "PARAMS_INVALID",
+    /// API method does not exist
+    MethodNotFound => "METHOD_NOT_FOUND"
+}
+
+
+impl ApiErrorKind {
+    /// `true` if the same request can succeed when repeated later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ApiErrorKind::FloodWait(_) | ApiErrorKind::PageSaveFailed)
+    }
+
+    /// `true` if the access token is invalid or does not grant access to the page.
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self, ApiErrorKind::AccessTokenInvalid | ApiErrorKind::PageAccessDenied)
+    }
+
+    /// Seconds to wait before the next request for [`ApiErrorKind::FloodWait`].
+    pub fn flood_wait(&self) -> Option<u64> {
+        match self {
+            ApiErrorKind::FloodWait(seconds) => Some(*seconds),
+            _ => None
+        }
+    }
+}
+
+
+impl From<String> for ApiErrorKind {
+    fn from(code: String) -> Self {
+        ApiErrorKind::from_code(&code)
+    }
+}
+
+
+impl From<&str> for ApiErrorKind {
+    fn from(code: &str) -> Self {
+        ApiErrorKind::from_code(code)
+    }
+}
+
+
+impl fmt::Display for ApiErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
 
 /// Enum of lib errors
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 pub enum TelegraphError {
     /// Telegrapth API error
-    ApiError(String),
+    ApiError(ApiErrorKind),
     /// Error occurred when sending the request
     #[serde(skip)]
     RequestError(reqwest::Error),
@@ -20,7 +162,28 @@ pub enum TelegraphError {
     ParseError(serde_json::Error),
     /// Error occurred when working with files
     #[serde(skip)]
-    IoError(io::Error)
+    IoError(io::Error),
+    /// Error occurred when converting page content
+    #[serde(skip)]
+    ContentError(String),
+    /// Page content failed client-side validation
+    #[serde(skip)]
+    InvalidContent(Vec<ContentIssue>),
+    /// File rejected before uploading
+    #[cfg(feature = "upload")]
+    #[serde(skip)]
+    InvalidUpload(UploadIssue),
+    /// Views range rejected before sending requests
+    #[serde(skip)]
+    InvalidViewsRange(ViewsRangeIssue),
+    /// Account created or token revoked, but saving it to the
+    /// [`AccountStore`][crate::AccountStore] failed with the boxed error.
+    /// The account holds the new access token, which the API does not return again.
+    #[serde(skip)]
+    AccountNotSaved(Box<Account>, Box<TelegraphError>),
+    /// Page series failed after creating some of its pages
+    #[serde(skip)]
+    IncompleteSeries(Box<IncompleteSeries>)
 }
 
 
@@ -31,6 +194,58 @@ impl fmt::Display for TelegraphError {
             TelegraphError::RequestError(error) => write!(f, "Request error: {}", error),
             TelegraphError::ParseError(error) => write!(f, "Parse error: {}", error),
             TelegraphError::IoError(error) => write!(f, "IO error: {}", error),
+            TelegraphError::ContentError(error) => write!(f, "Content error: {}", error),
+            TelegraphError::InvalidContent(issues) => {
+                write!(f, "Invalid content: ")?;
+                for (index, issue) in issues.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", issue)?;
+                }
+                Ok(())
+            },
+            #[cfg(feature = "upload")]
+            TelegraphError::InvalidUpload(issue) => write!(f, "Invalid upload: {}", issue),
+            TelegraphError::InvalidViewsRange(issue) => write!(f, "Invalid views range: {}", issue),
+            TelegraphError::AccountNotSaved(_, error) => write!(f, "Account not saved: {}", error),
+            TelegraphError::IncompleteSeries(incomplete) => write!(
+                f, "Incomplete series, {} of {} parts created: {}",
+                incomplete.series.parts.len(), incomplete.count, incomplete.error
+            ),
+        }
+    }
+}
+
+
+impl TelegraphError {
+    /// Kind of the API error, `None` for other errors.
+    pub fn api_error(&self) -> Option<&ApiErrorKind> {
+        match self {
+            TelegraphError::ApiError(kind) => Some(kind),
+            _ => None
+        }
+    }
+
+    /// `true` for retryable API errors and for timeouts or connection failures.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TelegraphError::ApiError(kind) => kind.is_retryable(),
+            TelegraphError::RequestError(error) => error.is_timeout() || error.is_connect(),
+            _ => false
+        }
+    }
+
+    /// `true` if the API rejected the access token.
+    pub fn is_auth_error(&self) -> bool {
+        self.api_error().is_some_and(ApiErrorKind::is_auth_error)
+    }
+
+    /// Account returned by the API for [`TelegraphError::AccountNotSaved`], `None` for other errors.
+    pub fn unsaved_account(&self) -> Option<&Account> {
+        match self {
+            TelegraphError::AccountNotSaved(account, _) => Some(account),
+            _ => None
         }
     }
 }
@@ -43,6 +258,13 @@ impl error::Error for TelegraphError {
             TelegraphError::ApiError(_) => None,
             TelegraphError::ParseError(error) => Some(error),
             TelegraphError::IoError(error) => Some(error),
+            TelegraphError::ContentError(_) => None,
+            TelegraphError::InvalidContent(_) => None,
+            #[cfg(feature = "upload")]
+            TelegraphError::InvalidUpload(_) => None,
+            TelegraphError::InvalidViewsRange(_) => None,
+            TelegraphError::AccountNotSaved(_, error) => Some(error.as_ref()),
+            TelegraphError::IncompleteSeries(incomplete) => Some(&incomplete.error),
         }
     }
 }
@@ -65,3 +287,32 @@ impl From<io::Error> for TelegraphError {
         TelegraphError::IoError(error)
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{Account, TelegraphResult};
+
+    use super::{ApiErrorKind, TelegraphError};
+
+    #[test]
+    fn api_error_kinds() {
+        assert_eq!(ApiErrorKind::from_code("PAGE_NOT_FOUND"), ApiErrorKind::PageNotFound);
+        assert_eq!(ApiErrorKind::from_code("FLOOD_WAIT_7"), ApiErrorKind::FloodWait(7));
+        assert_eq!(ApiErrorKind::from_code("FLOOD_WAIT_X"), ApiErrorKind::Unknown("FLOOD_WAIT_X".into()));
+        assert_eq!(ApiErrorKind::FloodWait(7).code(), "FLOOD_WAIT_7");
+        assert_eq!(ApiErrorKind::Unknown("NEW_ERROR".into()).to_string(), "NEW_ERROR");
+        assert!(ApiErrorKind::FloodWait(7).is_retryable());
+        assert!(ApiErrorKind::AccessTokenInvalid.is_auth_error());
+        assert!(!ApiErrorKind::TitleRequired.is_retryable());
+    }
+
+    #[test]
+    fn api_error_deserialize() {
+        let json: TelegraphResult<Account> = serde_json::from_str(r#"{"ok": false, "error": "ACCESS_TOKEN_INVALID"}"#).unwrap();
+        let error = json.error.unwrap();
+        assert!(matches!(error, TelegraphError::ApiError(ApiErrorKind::AccessTokenInvalid)));
+        assert!(error.is_auth_error());
+        assert_eq!(error.to_string(), "Api error: ACCESS_TOKEN_INVALID");
+    }
+}
@@ -0,0 +1,182 @@
+//! Offline archive of the pages of an account
+//!
+//! [`Export`] walks all pages of an account with `getPageList`,
+//! fetches every page with its content and writes the node tree
+//! to `pages/<path>.json`, optionally rendered to `pages/<path>.html`
+//! and `pages/<path>.md`. The [`ExportIndex`] of the pages is written to [`INDEX_FILE`].
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::content::{to_html, to_markdown};
+use crate::store::write_atomic;
+use crate::types::Page;
+use crate::{Telegraph, TelegraphError};
+
+
+/// Name of the index file in the export directory
+pub const INDEX_FILE: &str = "index.json";
+
+
+/// Exported page
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedPage {
+    /// Title of the page
+    pub title: String,
+    /// Path of the page
+    pub path: String,
+    /// URL of the page
+    pub url: String,
+    /// Number of page views
+    pub views: u32,
+    /// Name of the author
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_name: Option<String>,
+    /// Profile link of the author
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_url: Option<String>,
+    /// Image URL of the page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    /// Written files, relative to the export directory
+    pub files: Vec<String>
+}
+
+
+/// Index of an export, in the order of `getPageList`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportIndex {
+    /// Exported pages
+    pub pages: Vec<ExportedPage>
+}
+
+
+impl ExportIndex {
+    /// Loads the index of the export in `dir`.
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self, TelegraphError> {
+        let text = fs::read_to_string(dir.as_ref().join(INDEX_FILE))?;
+        Ok(serde_json::from_str(&text)?)
+    }
+}
+
+
+/// Export of an account, see [`Telegraph::export`]
+#[derive(Clone)]
+pub struct Export {
+    telegraph: Telegraph,
+    access_token: String,
+    dir: PathBuf,
+    html: bool,
+    markdown: bool
+}
+
+
+impl Export {
+    pub(crate) fn new(telegraph: Telegraph, access_token: &str, dir: &Path) -> Self {
+        Export { telegraph, access_token: access_token.into(), dir: dir.into(), html: false, markdown: false }
+    }
+
+    /// If `true`, every page is also rendered to HTML.
+    pub fn html(mut self, html: bool) -> Self {
+        self.html = html;
+        self
+    }
+
+    /// If `true`, every page is also rendered to Markdown.
+    pub fn markdown(mut self, markdown: bool) -> Self {
+        self.markdown = markdown;
+        self
+    }
+
+    /// Fetches and writes all pages, then the index.
+    /// Stops at the first error.
+    pub fn run(&self) -> Result<ExportIndex, TelegraphError> {
+        fs::create_dir_all(self.dir.join("pages"))?;
+        let mut index = ExportIndex::default();
+        for page in self.telegraph.pages(&self.access_token) {
+            let page = self.telegraph.get_page()
+                .path(&page?.path)
+                .return_content(true)
+                .send()?;
+            index.pages.push(self.write_page(page)?);
+        }
+        write_atomic(&self.dir.join(INDEX_FILE), serde_json::to_string_pretty(&index)?.as_bytes())?;
+        Ok(index)
+    }
+
+    fn write_page(&self, page: Page) -> Result<ExportedPage, TelegraphError> {
+        let content = page.content.unwrap_or_default();
+        let name = format!("pages/{}", page.path.replace(['/', '\\'], "_"));
+        let mut files = vec![(format!("{}.json", name), serde_json::to_string_pretty(&content)?)];
+        if self.html {
+            files.push((format!("{}.html", name), to_html(&content)));
+        }
+        if self.markdown {
+            files.push((format!("{}.md", name), to_markdown(&content)));
+        }
+        for (file, text) in &files {
+            write_atomic(&self.dir.join(file), text.as_bytes())?;
+        }
+        Ok(ExportedPage {
+            title: page.title,
+            path: page.path,
+            url: page.url,
+            views: page.views,
+            author_name: page.author_name,
+            author_url: page.author_url,
+            image_url: page.image_url,
+            files: files.into_iter().map(|(file, _)| file).collect()
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::build_content;
+    use crate::testing::FakeTelegraph;
+    use crate::types::Node;
+
+    use super::ExportIndex;
+
+    #[test]
+    fn export_account() {
+        let dir = std::env::temp_dir().join(format!("telegraph-export-{}", std::process::id()));
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let token = telegraph.create_account().short_name("Export").send().unwrap().access_token.unwrap();
+        let content = build_content(r#"[{"tag": "p", "children": ["Hello ", {"tag": "b", "children": ["world"]}]}]"#).unwrap();
+        let mut paths = vec![];
+        for title in ["First", "Second"] {
+            let page = telegraph.create_page()
+                .access_token(&token)
+                .title(title)
+                .author_name("Author")
+                .content(content.clone())
+                .send()
+                .unwrap();
+            paths.push(page.path);
+        }
+        server.add_views(&paths[0], 2024, 1, 1, 0, 7);
+
+        let index = telegraph.export(&token, &dir).html(true).markdown(true).run().unwrap();
+        assert_eq!(ExportIndex::load(&dir).unwrap(), index);
+        assert_eq!(index.pages.len(), 2);
+        let first = index.pages.iter().find(|page| page.path == paths[0]).unwrap();
+        assert_eq!((first.title.as_str(), first.views), ("First", 7));
+        assert_eq!(first.author_name.as_deref(), Some("Author"));
+        assert_eq!(first.files.len(), 3);
+
+        let name = format!("pages/{}", paths[0]);
+        let json: Vec<Node> = serde_json::from_str(&fs::read_to_string(dir.join(format!("{}.json", name))).unwrap()).unwrap();
+        assert_eq!(json, content);
+        assert_eq!(fs::read_to_string(dir.join(format!("{}.html", name))).unwrap(), "<p>Hello <b>world</b></p>");
+        assert_eq!(fs::read_to_string(dir.join(format!("{}.md", name))).unwrap().trim(), "Hello **world**");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -181,65 +181,205 @@
 //! # Errors
 //! 
 //! Possible errors are described in [`TelegraphError`].
+//!
+//! # Async
+//!
+//! With the `async` feature enabled, `AsyncTelegraph` exposes the same builders
+//! with `async fn send()`, backed by the non-blocking [`reqwest::Client`].
 
+mod macros;
 pub mod types;
 pub mod requests;
 pub mod error;
+pub mod content;
+pub mod retry;
+pub mod store;
+#[cfg(feature = "markdown")]
+pub mod dir_sync;
+pub mod export;
+pub mod series;
+#[cfg(feature = "upload")]
+pub mod upload;
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
-use std::rc::Rc;
+use std::sync::Arc;
 use std::path::Path;
-use std::fs::File;
-use std::io::Read;
 
 use reqwest::blocking::{Client, multipart};
 use types::Node;
 #[cfg(feature = "upload")]
 use types::{UploadResult, Media};
+#[cfg(feature = "upload")]
+use upload::UploadFile;
 
 use crate::requests::{
     CreateAccount, EditAccountInfo, GetAccountInfo, 
     CreatePage, RevokeAccessToken, EditPage, GetPage,
     GetPageList, GetViews, NoShortName, NoAccessToken,
-    NoTitle, NoContent, NoPath
+    NoTitle, NoContent, NoPath, Pages,
+    Granularity, ViewsDate, ViewsRange
 };
+#[cfg(feature = "upload")]
+use crate::requests::Content;
 pub use crate::error::TelegraphError;
+pub use crate::retry::RetryPolicy;
+pub use crate::store::AccountStore;
+#[cfg(feature = "async")]
+pub use crate::asynchronous::AsyncTelegraph;
 
 
+/// Default Telegraph API base url
+pub const DEFAULT_API_BASE: &str = "https://api.telegra.ph";
+/// Default Telegraph upload url
+pub const DEFAULT_UPLOAD_URL: &str = "https://telegra.ph/upload";
+
+
+#[derive(Clone)]
 struct MethodName {
-    create_account: Rc<String>,
-    edit_account_info: Rc<String>,
-    get_account_info: Rc<String>,
-    revoke_access_token: Rc<String>,
-    create_page: Rc<String>,
-    edit_page: Rc<String>,
-    get_page: Rc<String>,
-    get_page_list: Rc<String>,
-    get_views: Rc<String>
+    create_account: Arc<String>,
+    edit_account_info: Arc<String>,
+    get_account_info: Arc<String>,
+    revoke_access_token: Arc<String>,
+    create_page: Arc<String>,
+    edit_page: Arc<String>,
+    get_page: Arc<String>,
+    get_page_list: Arc<String>,
+    get_views: Arc<String>,
+    #[cfg_attr(not(feature = "upload"), allow(dead_code))]
+    upload: Arc<String>
+}
+
+
+impl MethodName {
+    fn new(api_base: &str, upload_url: &str) -> Self {
+        let api_base = api_base.trim_end_matches('/');
+        let method = |name: &str| Arc::new(format!("{}/{}", api_base, name));
+        MethodName {
+            create_account: method("createAccount"),
+            edit_account_info: method("editAccountInfo"),
+            get_account_info: method("getAccountInfo"),
+            revoke_access_token: method("revokeAccessToken"),
+            create_page: method("createPage"),
+            edit_page: method("editPage"),
+            get_page: method("getPage"),
+            get_page_list: method("getPageList"),
+            get_views: method("getViews"),
+            upload: Arc::new(upload_url.to_string()),
+        }
+    }
 }
 
 
 impl Default for MethodName{
     fn default() -> Self {
-        MethodName {
-            create_account: Rc::new("https://api.telegra.ph/createAccount".to_string()),
-            edit_account_info: Rc::new("https://api.telegra.ph/editAccountInfo".to_string()),
-            get_account_info: Rc::new("https://api.telegra.ph/getAccountInfo".to_string()),
-            revoke_access_token: Rc::new("https://api.telegra.ph/revokeAccessToken".to_string()),
-            create_page: Rc::new("https://api.telegra.ph/createPage".to_string()),
-            edit_page: Rc::new("https://api.telegra.ph/editPage".to_string()),
-            get_page: Rc::new("https://api.telegra.ph/getPage".to_string()),
-            get_page_list: Rc::new("https://api.telegra.ph/getPageList".to_string()),
-            get_views: Rc::new("https://api.telegra.ph/getViews".to_string()),
+        MethodName::new(DEFAULT_API_BASE, DEFAULT_UPLOAD_URL)
+    }
+}
+
+
+/// Builder of [`Telegraph`] with custom API urls and http client
+///
+/// # Example
+/// ```rust, no_run
+/// use telegraph_api_rs::Telegraph;
+///
+/// let telegraph = Telegraph::builder()
+/// .api_base("http://127.0.0.1:8080")
+/// .upload_url("http://127.0.0.1:8080/upload")
+/// .build();
+/// ```
+pub struct TelegraphBuilder {
+    api_base: String,
+    upload_url: String,
+    client: Option<Client>,
+    retry_policy: RetryPolicy,
+    account_store: Option<AccountStore>
+}
+
+
+impl Default for TelegraphBuilder {
+    fn default() -> Self {
+        TelegraphBuilder {
+            api_base: DEFAULT_API_BASE.to_string(),
+            upload_url: DEFAULT_UPLOAD_URL.to_string(),
+            client: None,
+            retry_policy: RetryPolicy::none(),
+            account_store: None
         }
     }
 }
 
 
+impl TelegraphBuilder {
+    /// Base url of the API methods, `https://api.telegra.ph` by default.
+    /// Every method url is built as `{api_base}/{methodName}`.
+    pub fn api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.into();
+        self
+    }
+
+    /// Url of the upload endpoint, `https://telegra.ph/upload` by default.
+    pub fn upload_url(mut self, upload_url: &str) -> Self {
+        self.upload_url = upload_url.into();
+        self
+    }
+
+    /// Custom http client used to send requests.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Policy of retrying failed requests, [`RetryPolicy::none`] by default.
+    /// See [`retry`][crate::retry] for the retried methods.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Store saving the accounts created and the tokens revoked with this client.
+    /// See [`store`][crate::store].
+    pub fn account_store(mut self, account_store: AccountStore) -> Self {
+        self.account_store = Some(account_store);
+        self
+    }
+
+    /// Constructs a new [`Telegraph`]
+    pub fn build(self) -> Telegraph {
+        Telegraph {
+            client: Arc::new(self.client.unwrap_or_default()),
+            method_name: MethodName::new(&self.api_base, &self.upload_url),
+            retry_policy: self.retry_policy,
+            account_store: self.account_store
+        }
+    }
+
+    #[cfg(feature = "async")]
+    /// Constructs a new `AsyncTelegraph` with the same urls.
+    /// The custom blocking client is not used by the async client.
+    pub fn build_async(self) -> AsyncTelegraph {
+        AsyncTelegraph::with_method_name(
+            MethodName::new(&self.api_base, &self.upload_url),
+            self.retry_policy,
+            self.account_store
+        )
+    }
+}
+
+
 /// `Telegraph` for calling method builder
-#[derive(Default)]
+///
+/// `Telegraph` is `Send + Sync` and cheap to clone: clones share the same http client,
+/// so one instance can be used from several threads or kept in a `static`.
+#[derive(Clone, Default)]
 pub struct Telegraph {
-    client: Rc<Client>,
-    method_name: MethodName
+    client: Arc<Client>,
+    method_name: MethodName,
+    retry_policy: RetryPolicy,
+    account_store: Option<AccountStore>
 }
 
 
@@ -249,6 +389,20 @@ impl Telegraph {
         Telegraph::default()
     }
 
+    /// Constructs a new [`TelegraphBuilder`] to configure API urls
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// use telegraph_api_rs::Telegraph;
+    ///
+    /// let telegraph = Telegraph::builder()
+    /// .api_base("http://127.0.0.1:8080")
+    /// .build();
+    /// ```
+    pub fn builder() -> TelegraphBuilder {
+        TelegraphBuilder::default()
+    }
+
     /// Use this method to create a new Telegraph [`Account`][crate::types::Account]. 
     /// Most users only need one account, but this can be useful 
     /// for channel administrators who would like to keep individual 
@@ -270,7 +424,8 @@ impl Telegraph {
     pub fn create_account(&self) -> CreateAccount<NoShortName> {
         CreateAccount::new(
             self.client.clone(), 
-            self.method_name.create_account.clone()
+            self.method_name.create_account.clone(),
+            self.account_store.clone()
         )
     }
 
@@ -295,7 +450,8 @@ impl Telegraph {
     {
         EditAccountInfo::new(
             self.client.clone(), 
-            self.method_name.edit_account_info.clone()
+            self.method_name.edit_account_info.clone(),
+            self.retry_policy
         )
     }
 
@@ -317,7 +473,8 @@ impl Telegraph {
     pub fn get_account_info(&self) -> GetAccountInfo<NoAccessToken> {
         GetAccountInfo::new(
             self.client.clone(), 
-            self.method_name.get_account_info.clone()
+            self.method_name.get_account_info.clone(),
+            self.retry_policy
         )
     }
 
@@ -341,7 +498,8 @@ impl Telegraph {
     pub fn revoke_access_token(&self) -> RevokeAccessToken<NoAccessToken> {
         RevokeAccessToken::new(
             self.client.clone(), 
-            self.method_name.revoke_access_token.clone()
+            self.method_name.revoke_access_token.clone(),
+            self.account_store.clone()
         )
     }
 
@@ -389,7 +547,8 @@ impl Telegraph {
     pub fn create_page(&self) -> CreatePage<NoAccessToken, NoTitle, NoContent> {
         CreatePage::new(
             self.client.clone(), 
-            self.method_name.create_page.clone()
+            self.method_name.create_page.clone(),
+            self.retry_policy
         )
     }
 
@@ -443,7 +602,8 @@ impl Telegraph {
     pub fn edit_page(&self) -> EditPage<NoAccessToken, NoPath, NoTitle, NoContent> {
         EditPage::new(
             self.client.clone(), 
-            self.method_name.edit_page.clone()
+            self.method_name.edit_page.clone(),
+            self.retry_policy
         )
     }
 
@@ -464,7 +624,8 @@ impl Telegraph {
     pub fn get_page(&self) -> GetPage<NoPath> {
         GetPage::new(
             self.client.clone(), 
-            self.method_name.get_page.clone()
+            self.method_name.get_page.clone(),
+            self.retry_policy
         )
     }
 
@@ -486,7 +647,8 @@ impl Telegraph {
     pub fn get_page_list(&self) -> GetPageList<NoAccessToken> {
         GetPageList::new(
             self.client.clone(), 
-            self.method_name.get_page_list.clone()
+            self.method_name.get_page_list.clone(),
+            self.retry_policy
         )
     }
 
@@ -510,43 +672,231 @@ impl Telegraph {
     pub fn get_views(&self) -> GetViews<NoPath> {
         GetViews::new(
             self.client.clone(), 
-            self.method_name.get_views.clone()
+            self.method_name.get_views.clone(),
+            self.retry_policy
         )
     }
 
-    #[cfg(feature = "upload")]
-    fn get_mime<T>(path: T) -> String 
-    where T: AsRef<Path>
-    {
-        let mime = mime_guess::from_path(path).first_or(mime_guess::mime::IMAGE_JPEG);
-        format!("{}/{}", mime.type_(), mime.subtype())
+    /// Iterates over all pages of the account, most recently created first.
+    /// Pages are fetched lazily in batches of [`GetPageList`] requests.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use telegraph_api_rs::{Telegraph, types::Account};
+    /// # let telegraph = Telegraph::new();
+    /// # let account = Account::default();
+    /// for page in telegraph.pages(&account.access_token.unwrap()) {
+    ///     println!("{}", page.unwrap().url);
+    /// }
+    /// ```
+    pub fn pages(&self, access_token: &str) -> Pages {
+        Pages::new(
+            self.client.clone(),
+            self.method_name.get_page_list.clone(),
+            self.retry_policy,
+            access_token
+        )
+    }
+
+    /// Number of page views from `from` to `to` inclusive,
+    /// one `getViews` request per hour, day or month.
+    /// Returns the series of the bucket starts and views, ordered by date.
+    ///
+    /// Dates outside 2000-2100, an end before the start or more than
+    /// [`MAX_VIEWS_BUCKETS`][crate::requests::MAX_VIEWS_BUCKETS] buckets
+    /// are rejected with [`TelegraphError::InvalidViewsRange`] without sending requests.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use telegraph_api_rs::Telegraph;
+    /// use telegraph_api_rs::requests::{Granularity, ViewsDate};
+    /// # let telegraph = Telegraph::new();
+    /// let daily = telegraph.views_range(
+    ///     "Sample-Page-12-15",
+    ///     ViewsDate::new(2024, 1, 1),
+    ///     ViewsDate::new(2024, 1, 31),
+    ///     Granularity::Day
+    /// ).unwrap();
+    /// for (date, views) in daily {
+    ///     println!("{}: {}", date, views);
+    /// }
+    /// ```
+    pub fn views_range(
+        &self,
+        path: &str,
+        from: ViewsDate,
+        to: ViewsDate,
+        granularity: Granularity
+    ) -> Result<Vec<(ViewsDate, u32)>, TelegraphError> {
+        ViewsRange::new(
+            self.client.clone(),
+            self.method_name.get_views.clone(),
+            self.retry_policy,
+            path, from, to, granularity
+        )?.send()
+    }
+
+    #[cfg(feature = "markdown")]
+    /// Mirrors the Markdown files of `dir` to pages of the account,
+    /// see [`dir_sync`][crate::dir_sync].
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use telegraph_api_rs::{Telegraph, types::Account};
+    /// # let telegraph = Telegraph::new();
+    /// # let account = Account::default();
+    /// let report = telegraph.sync_dir(&account.access_token.unwrap(), "docs")
+    ///     .author_name("Author name")
+    ///     .run()
+    ///     .unwrap();
+    /// for file in report.files {
+    ///     println!("{:?} {} -> {}", file.action, file.file, file.path);
+    /// }
+    /// ```
+    pub fn sync_dir<P: AsRef<Path>>(&self, access_token: &str, dir: P) -> dir_sync::DirSync {
+        dir_sync::DirSync::new(self.clone(), access_token, dir.as_ref())
+    }
+
+    /// Writes all pages of the account with their content to `dir`,
+    /// see [`export`][crate::export].
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use telegraph_api_rs::{Telegraph, types::Account};
+    /// # let telegraph = Telegraph::new();
+    /// # let account = Account::default();
+    /// let index = telegraph.export(&account.access_token.unwrap(), "backup")
+    ///     .markdown(true)
+    ///     .run()
+    ///     .unwrap();
+    /// println!("{} pages exported", index.pages.len());
+    /// ```
+    pub fn export<P: AsRef<Path>>(&self, access_token: &str, dir: P) -> export::Export {
+        export::Export::new(self.clone(), access_token, dir.as_ref())
+    }
+
+    /// Publishes content bigger than one page as a series of linked pages,
+    /// see [`series`][crate::series].
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use telegraph_api_rs::{Telegraph, content::from_html};
+    /// # let telegraph = Telegraph::new();
+    /// # let token = "";
+    /// # let html = "";
+    /// let content = from_html(html).unwrap();
+    /// let series = telegraph.create_page_series(token, "Annual report", content)
+    ///     .index(true)
+    ///     .send()
+    ///     .unwrap();
+    /// println!("{}", series.index.unwrap().url);
+    /// ```
+    pub fn create_page_series(&self, access_token: &str, title: &str, content: Vec<Node>) -> series::PageSeries {
+        series::PageSeries::new(self.clone(), access_token, title, content)
     }
 
     #[cfg(feature = "upload")]
-    fn _upload<T>(client: &Client, files: &[T]) -> Result<Vec<Media>, TelegraphError> 
-    where T: AsRef<Path>
-    {
+    fn _upload(client: &Client, url: &str, files: Vec<UploadFile>) -> Result<Vec<Media>, TelegraphError> {
         let mut form = multipart::Form::new();
-        for (index, file_name) in files.iter().enumerate() {
-            let mut buf = vec![];
-            let mut file = File::open(file_name)?;
-            file.read_to_end(&mut buf)?;
-            let part = multipart::Part::bytes(buf)
-                .file_name(index.to_string())
-                .mime_str(&Self::get_mime(file_name))?;
-            form = form.part(index.to_string(), part);
+        for (index, file) in files.into_iter().enumerate() {
+            form = form.part(index.to_string(), file.into_part()?);
         }
 
-        let response = client.post("https://telegra.ph/upload")
+        let response = client.post(url)
         .multipart(form)
         .send()?;
         
         match response.json::<UploadResult>()? {
-            UploadResult::Error { error } => Err(TelegraphError::ApiError(error)),
+            UploadResult::Error { error } => Err(TelegraphError::ApiError(error.into())),
             UploadResult::Ok(vec) => Ok(vec)
         }
     }
 
+    #[cfg(feature = "upload")]
+    fn read_files<T>(files: &[T]) -> Result<Vec<UploadFile>, TelegraphError>
+    where T: AsRef<Path>
+    {
+        files.iter().map(UploadFile::from_path).collect()
+    }
+
+    #[cfg(feature = "upload")]
+    /// Upload files from memory to telegraph
+    ///
+    /// # Example
+    /// ``` rust, no_run
+    /// # use telegraph_api_rs::Telegraph;
+    /// use telegraph_api_rs::upload::UploadFile;
+    ///
+    /// # let image_bytes = vec![];
+    /// let telegraph = Telegraph::new();
+    /// let files = vec![UploadFile::from_bytes(image_bytes, "image.png")];
+    /// let media = telegraph.upload_files(files);
+    /// ```
+    pub fn upload_files(&self, files: Vec<UploadFile>) -> Result<Vec<Media>, TelegraphError> {
+        Self::_upload(&self.client, &self.method_name.upload, files)
+    }
+
+    #[cfg(feature = "upload")]
+    /// Upload files from memory to telegraph with custom client
+    pub fn upload_files_with(client: &Client, files: Vec<UploadFile>) -> Result<Vec<Media>, TelegraphError> {
+        Self::_upload(client, DEFAULT_UPLOAD_URL, files)
+    }
+
+    #[cfg(feature = "upload")]
+    /// Uploads local files referenced by `img` and `video` elements
+    /// (see [`local_media`][crate::content::local_media]) with one request
+    /// and rewrites their `src` to the uploaded files.
+    /// Relative paths are resolved against `base_dir`.
+    /// Returns the number of uploaded files.
+    pub fn upload_local_media<P>(&self, content: &mut [Node], base_dir: P) -> Result<usize, TelegraphError>
+    where P: AsRef<Path>
+    {
+        let sources = content::local_media(content);
+        if sources.is_empty() {
+            return Ok(0);
+        }
+        let paths: Vec<_> = sources.iter().map(|src| base_dir.as_ref().join(src)).collect();
+        let media = self.upload(&paths)?;
+        upload::apply_media(content, &sources, &media);
+        Ok(sources.len())
+    }
+
+    #[cfg(feature = "upload")]
+    /// [`create_page`][Telegraph::create_page] with the content
+    /// after uploading its local media with [`upload_local_media`][Telegraph::upload_local_media].
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use telegraph_api_rs::{Telegraph, content::from_html};
+    /// # let telegraph = Telegraph::new();
+    /// # let token = "";
+    /// let content = from_html(r#"<p>Chart</p><img src="figures/a.png">"#).unwrap();
+    /// let page = telegraph.create_page_with_media(content, "./article")
+    /// .unwrap()
+    /// .access_token(token)
+    /// .title("Report")
+    /// .send()
+    /// .unwrap();
+    /// ```
+    pub fn create_page_with_media<P>(&self, mut content: Vec<Node>, base_dir: P)
+        -> Result<CreatePage<NoAccessToken, NoTitle, Content>, TelegraphError>
+    where P: AsRef<Path>
+    {
+        self.upload_local_media(&mut content, base_dir)?;
+        Ok(self.create_page().content(content))
+    }
+
+    #[cfg(feature = "upload")]
+    /// [`edit_page`][Telegraph::edit_page] with the content
+    /// after uploading its local media with [`upload_local_media`][Telegraph::upload_local_media].
+    pub fn edit_page_with_media<P>(&self, mut content: Vec<Node>, base_dir: P)
+        -> Result<EditPage<NoAccessToken, NoPath, NoTitle, Content>, TelegraphError>
+    where P: AsRef<Path>
+    {
+        self.upload_local_media(&mut content, base_dir)?;
+        Ok(self.edit_page().content(content))
+    }
+
     #[cfg(feature = "upload")]
     /// Upload files to telegraph
     /// 
@@ -560,7 +910,7 @@ impl Telegraph {
     pub fn upload<T>(&self, files: &[T]) -> Result<Vec<Media>, TelegraphError> 
     where T: AsRef<Path>
     {
-        Self::_upload(&self.client, files)
+        self.upload_files(Self::read_files(files)?)
     }
 
     #[cfg(feature = "upload")]
@@ -578,7 +928,7 @@ impl Telegraph {
     pub fn upload_with<T>(client: &Client, files: &[T]) -> Result<Vec<Media>, TelegraphError> 
     where T: AsRef<Path>
     {
-        Self::_upload(client, files)
+        Self::upload_files_with(client, Self::read_files(files)?)
     }
 
 }
@@ -620,3 +970,59 @@ impl Telegraph {
 pub fn build_content(content: &str) -> Result<Vec<Node>, TelegraphError> {
     serde_json::from_str(content).map_err(TelegraphError::from)
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Telegraph, MethodName};
+    use crate::requests::*;
+
+    fn assert_thread_safe<T: Send + Sync + Clone>() {}
+
+    #[test]
+    fn method_name_default() {
+        let method_name = MethodName::default();
+        assert_eq!(method_name.create_account.as_str(), "https://api.telegra.ph/createAccount");
+        assert_eq!(method_name.get_views.as_str(), "https://api.telegra.ph/getViews");
+        assert_eq!(method_name.upload.as_str(), "https://telegra.ph/upload");
+    }
+
+    #[test]
+    fn builder_custom_urls() {
+        let telegraph = Telegraph::builder()
+            .api_base("http://127.0.0.1:8080/")
+            .upload_url("http://127.0.0.1:8080/upload")
+            .build();
+        assert_eq!(telegraph.method_name.create_page.as_str(), "http://127.0.0.1:8080/createPage");
+        assert_eq!(telegraph.method_name.get_page_list.as_str(), "http://127.0.0.1:8080/getPageList");
+        assert_eq!(telegraph.method_name.upload.as_str(), "http://127.0.0.1:8080/upload");
+    }
+
+    #[test]
+    fn telegraph_is_thread_safe() {
+        assert_thread_safe::<Telegraph>();
+        assert_thread_safe::<CreateAccount<ShortName>>();
+        assert_thread_safe::<EditAccountInfo<AccessToken>>();
+        assert_thread_safe::<GetAccountInfo<AccessToken>>();
+        assert_thread_safe::<RevokeAccessToken<AccessToken>>();
+        assert_thread_safe::<CreatePage<AccessToken, Title, Content>>();
+        assert_thread_safe::<EditPage<AccessToken, Path, Title, Content>>();
+        assert_thread_safe::<GetPage<Path>>();
+        assert_thread_safe::<GetPageList<AccessToken>>();
+        assert_thread_safe::<GetViews<Path>>();
+
+        let telegraph = Telegraph::new();
+        std::thread::spawn(move || telegraph.get_page().path("Sample-Page-12-15")).join().unwrap();
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_telegraph_is_thread_safe() {
+        use reqwest::Client;
+
+        assert_thread_safe::<crate::AsyncTelegraph>();
+        assert_thread_safe::<CreatePage<AccessToken, Title, Content, Client>>();
+        assert_thread_safe::<EditPage<AccessToken, Path, Title, Content, Client>>();
+        assert_thread_safe::<GetViews<Path, Client>>();
+    }
+}
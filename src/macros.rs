@@ -0,0 +1,164 @@
+/// Build page content with a tag tree syntax
+///
+/// Every tag of [`NodeTag`][crate::types::NodeTag] is written by its name
+/// followed by its children in braces. `a` takes the `href`, `img`, `iframe`
+/// and `video` take the `src` in parentheses. String literals are text nodes
+/// and `{ expr }` inserts any value convertible into [`Node`][crate::types::Node].
+///
+/// # Example
+/// ```rust
+/// use telegraph_api_rs::telegraph_content;
+///
+/// let name = "world";
+/// let content = telegraph_content! {
+///     h3 { "Title" }
+///     p { b { "bold" } " text " a("https://telegra.ph") { "link" } }
+///     figure { img("/file/1.jpg") figcaption { "Hello " { name } } }
+///     hr
+/// };
+/// assert_eq!(content.len(), 4);
+/// ```
+#[macro_export]
+macro_rules! telegraph_content {
+    ($($body:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut nodes: ::std::vec::Vec<$crate::types::Node> = ::std::vec::Vec::new();
+        $crate::__telegraph_content_chunks!(nodes; []; $($body)*);
+        nodes
+    }};
+}
+
+
+/// Splits the body into chunks of 32 tokens, so the recursion depth grows
+/// with the chunk count instead of the sibling count
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __telegraph_content_chunks {
+    // Argument and children groups right after a cut belong to the chunk's last tag
+    ($nodes:ident; @cut [$($chunk:tt)*]; ( $($arg:tt)* ) { $($children:tt)* } $($rest:tt)*) => {
+        $crate::__telegraph_content_chunks!($nodes; [$($chunk)* ( $($arg)* ) { $($children)* }]; $($rest)*);
+    };
+    ($nodes:ident; @cut [$($chunk:tt)*]; ( $($arg:tt)* ) $($rest:tt)*) => {
+        $crate::__telegraph_content_chunks!($nodes; [$($chunk)* ( $($arg)* )]; $($rest)*);
+    };
+    ($nodes:ident; @cut [$($chunk:tt)*]; { $($children:tt)* } $($rest:tt)*) => {
+        $crate::__telegraph_content_chunks!($nodes; [$($chunk)* { $($children)* }]; $($rest)*);
+    };
+    ($nodes:ident; @cut [$($chunk:tt)*]; $($rest:tt)*) => {
+        $crate::__telegraph_content_chunks!($nodes; [$($chunk)*]; $($rest)*);
+    };
+    ($nodes:ident; [$($chunk:tt)*]; $t0:tt $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt $t9:tt $t10:tt $t11:tt $t12:tt $t13:tt $t14:tt $t15:tt $t16:tt $t17:tt $t18:tt $t19:tt $t20:tt $t21:tt $t22:tt $t23:tt $t24:tt $t25:tt $t26:tt $t27:tt $t28:tt $t29:tt $t30:tt $t31:tt $($rest:tt)*) => {
+        $crate::__telegraph_content_nodes!($nodes; $($chunk)*);
+        $crate::__telegraph_content_chunks!($nodes; @cut [$t0 $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8 $t9 $t10 $t11 $t12 $t13 $t14 $t15 $t16 $t17 $t18 $t19 $t20 $t21 $t22 $t23 $t24 $t25 $t26 $t27 $t28 $t29 $t30 $t31]; $($rest)*);
+    };
+    ($nodes:ident; [$($chunk:tt)*]; $($rest:tt)*) => {
+        $crate::__telegraph_content_nodes!($nodes; $($chunk)* $($rest)*);
+    };
+}
+
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __telegraph_content_nodes {
+    ($nodes:ident;) => {};
+    ($nodes:ident; $text:literal $($rest:tt)*) => {
+        ::std::vec::Vec::push(&mut $nodes, $crate::types::Node::from($text));
+        $crate::__telegraph_content_nodes!($nodes; $($rest)*);
+    };
+    ($nodes:ident; { $value:expr } $($rest:tt)*) => {
+        ::std::vec::Vec::push(&mut $nodes, $crate::types::Node::from($value));
+        $crate::__telegraph_content_nodes!($nodes; $($rest)*);
+    };
+    ($nodes:ident; $tag:ident ( $arg:expr ) { $($children:tt)* } $($rest:tt)*) => {
+        ::std::vec::Vec::push(&mut $nodes, $crate::types::Node::from(
+            $crate::types::Node::$tag($arg).children($crate::telegraph_content!($($children)*))
+        ));
+        $crate::__telegraph_content_nodes!($nodes; $($rest)*);
+    };
+    ($nodes:ident; $tag:ident ( $arg:expr ) $($rest:tt)*) => {
+        ::std::vec::Vec::push(&mut $nodes, $crate::types::Node::from($crate::types::Node::$tag($arg)));
+        $crate::__telegraph_content_nodes!($nodes; $($rest)*);
+    };
+    ($nodes:ident; $tag:ident { $($children:tt)* } $($rest:tt)*) => {
+        ::std::vec::Vec::push(&mut $nodes, $crate::types::Node::from(
+            $crate::types::Node::$tag().children($crate::telegraph_content!($($children)*))
+        ));
+        $crate::__telegraph_content_nodes!($nodes; $($rest)*);
+    };
+    ($nodes:ident; $tag:ident $($rest:tt)*) => {
+        ::std::vec::Vec::push(&mut $nodes, $crate::types::Node::from($crate::types::Node::$tag()));
+        $crate::__telegraph_content_nodes!($nodes; $($rest)*);
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+    use crate::types::Node;
+
+    #[test]
+    fn content_macro() {
+        let content = telegraph_content! {
+            h3 { "Title" }
+            p { b { "bold" } " text" br }
+            ul { li { "One" } li { a("https://telegra.ph") { "Two" } } }
+            video("/file/1.mp4")
+        };
+        let expected = build_content(r#"[
+            {"tag": "h3", "children": ["Title"]},
+            {"tag": "p", "children": [{"tag": "b", "children": ["bold"]}, " text", {"tag": "br"}]},
+            {"tag": "ul", "children": [
+                {"tag": "li", "children": ["One"]},
+                {"tag": "li", "children": [{"tag": "a", "attrs": {"href": "https://telegra.ph"}, "children": ["Two"]}]}
+            ]},
+            {"tag": "video", "attrs": {"src": "/file/1.mp4"}}
+        ]"#).unwrap();
+        assert_eq!(content, expected);
+        assert!(telegraph_content! {}.is_empty());
+    }
+
+
+    #[test]
+    fn content_macro_siblings() {
+        let content = telegraph_content!{ h3 { "Title" } p { b { "bold" } " text" } };
+        let expected = build_content(r#"[
+            {"tag": "h3", "children": ["Title"]},
+            {"tag": "p", "children": [{"tag": "b", "children": ["bold"]}, " text"]}
+        ]"#).unwrap();
+        assert_eq!(content, expected);
+    }
+
+
+    #[test]
+    fn content_macro_many_siblings() {
+        let content = telegraph_content! {
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+            a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br a("/") { "x" } br br
+        };
+        let expected: Vec<Node> = (0..120).flat_map(|_| [
+            Node::from(Node::a("/").children(vec![Node::from("x")])),
+            Node::from(Node::br()),
+            Node::from(Node::br()),
+        ]).collect();
+        assert_eq!(content, expected);
+    }
+}
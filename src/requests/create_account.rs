@@ -1,27 +1,32 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
 use serde::Serialize;
 
 use crate::types::{Account, TelegraphResult};
 use crate::error::TelegraphError;
+use crate::store::AccountStore;
 
 
 /// Short name of the empty state type 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct NoShortName;
 
 /// Short name of the filled state type
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct ShortName(String);
 
 /// Builder of `createAccount`
-#[derive(Default, Serialize)]
-pub struct CreateAccount<N> {
+#[derive(Clone, Default, Serialize)]
+pub struct CreateAccount<N, Cl = Client> {
     #[serde(skip)]
-    client: Rc<Client>,
+    client: Arc<Cl>,
     #[serde(skip)]
-    method_name: Rc<String>,
+    method_name: Arc<String>,
+    #[serde(skip)]
+    account_store: Option<AccountStore>,
 
     short_name: N,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -31,21 +36,26 @@ pub struct CreateAccount<N> {
 }
 
 
-impl CreateAccount<NoShortName> {
-    pub(crate) fn new(client: Rc<Client>, method_name: Rc<String>) -> CreateAccount<NoShortName> {
-        Self {  client, method_name, ..Self::default() }
+impl<Cl: Default> CreateAccount<NoShortName, Cl> {
+    pub(crate) fn new(
+        client: Arc<Cl>,
+        method_name: Arc<String>,
+        account_store: Option<AccountStore>
+    ) -> CreateAccount<NoShortName, Cl> {
+        Self { client, method_name, account_store, ..Self::default() }
     }
 }
 
 
-impl<N> CreateAccount<N> {
+impl<N, Cl> CreateAccount<N, Cl> {
     /// Required. Account name, helps users with several accounts remember 
     /// which they are currently using. Displayed to the user above the 
     /// "Edit/Publish" button on Telegra.ph, other users don't see this name.
-    pub fn short_name(self, short_name: &str) -> CreateAccount<ShortName> {
+    pub fn short_name(self, short_name: &str) -> CreateAccount<ShortName, Cl> {
         CreateAccount { 
             client: self.client,
             method_name: self.method_name,
+            account_store: self.account_store,
             short_name: ShortName(short_name.into()),
             author_name: self.author_name,
             author_url: self.author_url
@@ -70,13 +80,41 @@ impl<N> CreateAccount<N> {
 
 impl CreateAccount<ShortName> {
     /// Sending request to API
+    ///
+    /// If saving the account to the store fails, the account is returned
+    /// in [`TelegraphError::AccountNotSaved`].
     pub fn send(self) -> Result<Account, TelegraphError> {
         let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
         let json: TelegraphResult<Account> = req.json()?;
         if !json.ok {
-            Err(json.error.unwrap())
-        } else {
-            Ok(json.result.unwrap())
+            return Err(json.error.unwrap());
+        }
+        let account = json.result.unwrap();
+        if let Some(store) = &self.account_store {
+            if let Err(error) = store.insert(&account) {
+                return Err(TelegraphError::AccountNotSaved(Box::new(account), Box::new(error)));
+            }
+        }
+        Ok(account)
+    }
+}
+
+#[cfg(feature = "async")]
+impl CreateAccount<ShortName, AsyncClient> {
+    /// Sending request to API
+    ///
+    /// If saving the account to the store fails, the account is returned
+    /// in [`TelegraphError::AccountNotSaved`].
+    pub async fn send(self) -> Result<Account, TelegraphError> {
+        let req = self.client.post(self.method_name.as_str()).form(&self).send().await?;
+        let json: TelegraphResult<Account> = req.json().await?;
+        if !json.ok {
+            return Err(json.error.unwrap());
+        }
+        let account = json.result.unwrap();
+        match &self.account_store {
+            Some(store) => store.write_blocking(account, |store, account| store.insert(account).map(|_| ())).await,
+            None => Ok(account)
         }
     }
-}
\ No newline at end of file
+}
@@ -1,22 +1,27 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
 use serde::Serialize;
 
 use crate::error::TelegraphError;
+use crate::retry::RetryPolicy;
 use crate::requests::{ApiFieldSerializer, NoAccessToken, AccessToken, NoTitle, Title, NoContent, Content};
 use crate::types::{Node, Page, TelegraphResult};
 
 
 /// Builder of `createPage`
-#[derive(Default, Serialize)]
-pub struct CreatePage<A, T, C> 
+#[derive(Clone, Default, Serialize)]
+pub struct CreatePage<A, T, C, Cl = Client> 
 where C: Serialize
 {
     #[serde(skip)]
-    client: Rc<Client>,
+    client: Arc<Cl>,
     #[serde(skip)]
-    method_name: Rc<String>,
+    method_name: Arc<String>,
+    #[serde(skip)]
+    retry_policy: RetryPolicy,
 
     access_token: A,
     title: T,
@@ -26,55 +31,68 @@ where C: Serialize
     author_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     author_url: Option<String>,
-    return_content: bool
+    return_content: bool,
+    #[serde(skip)]
+    validate: bool,
+    #[serde(skip)]
+    retry: bool
 }
 
-impl CreatePage<NoAccessToken, NoTitle, NoContent> {
-    pub(crate) fn new(client: Rc<Client>, method_name: Rc<String>) -> CreatePage<NoAccessToken, NoTitle, NoContent> {
-        Self {  client, method_name, ..Self::default() }
+impl<Cl: Default> CreatePage<NoAccessToken, NoTitle, NoContent, Cl> {
+    pub(crate) fn new(client: Arc<Cl>, method_name: Arc<String>, retry_policy: RetryPolicy) -> CreatePage<NoAccessToken, NoTitle, NoContent, Cl> {
+        Self { client, method_name, retry_policy, ..Self::default() }
     }
 }
 
-impl<A, T, C: Serialize> CreatePage<A, T, C> {
+impl<A, T, C: Serialize, Cl> CreatePage<A, T, C, Cl> {
     /// Required. Access token of the Telegraph account.
-    pub fn access_token(self, access_token: &str) -> CreatePage<AccessToken, T, C> {
+    pub fn access_token(self, access_token: &str) -> CreatePage<AccessToken, T, C, Cl> {
         CreatePage { 
             client: self.client, 
             method_name: self.method_name, 
+            retry_policy: self.retry_policy,
             access_token: AccessToken(access_token.into()), 
             title: self.title, 
             content: self.content, 
             author_name: self.author_name, 
             author_url: self.author_url, 
-            return_content: self.return_content 
+            return_content: self.return_content,
+            validate: self.validate,
+            retry: self.retry
         }
     }
 
     /// Required. Page title.
-    pub fn title(self, title: &str) -> CreatePage<A, Title, C> {
+    pub fn title(self, title: &str) -> CreatePage<A, Title, C, Cl> {
         CreatePage { 
             client: self.client, 
             method_name: self.method_name, 
+            retry_policy: self.retry_policy,
             access_token: self.access_token, 
             title: Title(title.into()), 
             content: self.content, 
             author_name: self.author_name, 
             author_url: self.author_url, 
-            return_content: self.return_content 
+            return_content: self.return_content,
+            validate: self.validate,
+            retry: self.retry
         }
     }
 
     /// Required. Content of the page.
-    pub fn content(self, content: Vec<Node>) -> CreatePage<A, T, Content> {
+    pub fn content(self, content: Vec<Node>) -> CreatePage<A, T, Content, Cl> {
         CreatePage { 
             client: self.client, 
             method_name: self.method_name, 
+            retry_policy: self.retry_policy,
             access_token: self.access_token, 
             title: self.title, 
             content: Content(content), 
             author_name: self.author_name, 
             author_url: self.author_url, 
-            return_content: self.return_content 
+            return_content: self.return_content,
+            validate: self.validate,
+            retry: self.retry
         }
     }
 
@@ -96,17 +114,60 @@ impl<A, T, C: Serialize> CreatePage<A, T, C> {
         self.return_content = return_content;
         self
     }
+
+    /// If `true`, the content is checked with [`validate`][crate::content::validate]
+    /// before sending and [`TelegraphError::InvalidContent`] is returned instead of the request.
+    pub fn validate(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// If `true`, the request is repeated according to the [`RetryPolicy`] of the client.
+    /// `createPage` is not idempotent, a repeated request may create a duplicate page.
+    pub fn retry(mut self, retry: bool) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    fn retry_policy(&self) -> RetryPolicy {
+        if self.retry { self.retry_policy } else { RetryPolicy::none() }
+    }
 }
 
 impl CreatePage<AccessToken, Title, Content> {
     /// Sending request to API
     pub fn send(self) -> Result<Page, TelegraphError> {
-        let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
-        let json: TelegraphResult<Page> = req.json()?;
-        if !json.ok {
-            Err(json.error.unwrap())
-        } else {
-            Ok(json.result.unwrap())
+        if self.validate {
+            self.content.validate()?;
+        }
+        self.retry_policy().run(|| {
+            let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
+            let json: TelegraphResult<Page> = req.json()?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl CreatePage<AccessToken, Title, Content, AsyncClient> {
+    /// Sending request to API
+    pub async fn send(self) -> Result<Page, TelegraphError> {
+        if self.validate {
+            self.content.validate()?;
         }
+        let this = &self;
+        self.retry_policy().run_async(|| async move {
+            let req = this.client.post(this.method_name.as_str()).form(this).send().await?;
+            let json: TelegraphResult<Page> = req.json().await?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        }).await
     }
 }
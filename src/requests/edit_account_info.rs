@@ -1,20 +1,25 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
 use serde::Serialize;
 
 use crate::types::{Account, TelegraphResult};
 use crate::error::TelegraphError;
+use crate::retry::RetryPolicy;
 use crate::requests::{NoAccessToken, AccessToken};
 
 
 /// Builder of `editAccountInfo`
-#[derive(Default, Serialize)]
-pub struct EditAccountInfo<T> {
+#[derive(Clone, Default, Serialize)]
+pub struct EditAccountInfo<T, Cl = Client> {
     #[serde(skip)]
-    client: Rc<Client>,
+    client: Arc<Cl>,
     #[serde(skip)]
-    method_name: Rc<String>,
+    method_name: Arc<String>,
+    #[serde(skip)]
+    retry_policy: RetryPolicy,
 
     access_token: T,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -26,19 +31,20 @@ pub struct EditAccountInfo<T> {
 }
 
 
-impl EditAccountInfo<NoAccessToken> {
-    pub(crate) fn new(client: Rc<Client>, method_name: Rc<String>) -> EditAccountInfo<NoAccessToken> {
-        Self {  client, method_name, ..Self::default() }
+impl<Cl: Default> EditAccountInfo<NoAccessToken, Cl> {
+    pub(crate) fn new(client: Arc<Cl>, method_name: Arc<String>, retry_policy: RetryPolicy) -> EditAccountInfo<NoAccessToken, Cl> {
+        Self { client, method_name, retry_policy, ..Self::default() }
     }
 }
 
 
-impl<T> EditAccountInfo<T> {
+impl<T, Cl> EditAccountInfo<T, Cl> {
     /// Required. Access token of the Telegraph account.
-    pub fn access_token(self, access_token: &str) -> EditAccountInfo<AccessToken> {
+    pub fn access_token(self, access_token: &str) -> EditAccountInfo<AccessToken, Cl> {
         EditAccountInfo { 
             client: self.client, 
             method_name: self.method_name, 
+            retry_policy: self.retry_policy,
             access_token: AccessToken(access_token.into()), 
             short_name: self.short_name, 
             author_name: self.author_name, 
@@ -70,12 +76,31 @@ impl<T> EditAccountInfo<T> {
 impl EditAccountInfo<AccessToken> {
     /// Sending request to API
     pub fn send(self) -> Result<Account, TelegraphError> {
-        let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
-        let json: TelegraphResult<Account> = req.json()?;
-        if !json.ok {
-            Err(json.error.unwrap())
-        } else {
-            Ok(json.result.unwrap())
-        }
+        self.retry_policy.run(|| {
+            let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
+            let json: TelegraphResult<Account> = req.json()?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl EditAccountInfo<AccessToken, AsyncClient> {
+    /// Sending request to API
+    pub async fn send(self) -> Result<Account, TelegraphError> {
+        let this = &self;
+        self.retry_policy.run_async(|| async move {
+            let req = this.client.post(this.method_name.as_str()).form(this).send().await?;
+            let json: TelegraphResult<Account> = req.json().await?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        }).await
     }
 }
@@ -1,9 +1,12 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
 use serde::Serialize;
 
 use crate::error::TelegraphError;
+use crate::retry::RetryPolicy;
 use crate::requests::{
     ApiFieldSerializer, NoAccessToken, AccessToken,
     NoTitle, Title, NoContent, Content, NoPath, Path
@@ -12,14 +15,16 @@ use crate::types::{Node, Page, TelegraphResult};
 
 
 /// Builder of `editPage`
-#[derive(Default, Serialize)]
-pub struct EditPage<A, P, T, C> 
+#[derive(Clone, Default, Serialize)]
+pub struct EditPage<A, P, T, C, Cl = Client> 
 where C: Serialize
 {
     #[serde(skip)]
-    client: Rc<Client>,
+    client: Arc<Cl>,
     #[serde(skip)]
-    method_name: Rc<String>,
+    method_name: Arc<String>,
+    #[serde(skip)]
+    retry_policy: RetryPolicy,
 
     access_token: A,
     path: P,
@@ -30,75 +35,85 @@ where C: Serialize
     author_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     author_url: Option<String>,
-    return_content: bool
+    return_content: bool,
+    #[serde(skip)]
+    validate: bool
 }
 
 
-impl EditPage<NoAccessToken, NoPath, NoTitle, NoContent> {
-    pub(crate) fn new(client: Rc<Client>, method_name: Rc<String>) -> EditPage<NoAccessToken, NoPath, NoTitle, NoContent> {
-        Self {  client, method_name, ..Self::default() }
+impl<Cl: Default> EditPage<NoAccessToken, NoPath, NoTitle, NoContent, Cl> {
+    pub(crate) fn new(client: Arc<Cl>, method_name: Arc<String>, retry_policy: RetryPolicy) -> EditPage<NoAccessToken, NoPath, NoTitle, NoContent, Cl> {
+        Self { client, method_name, retry_policy, ..Self::default() }
     }
 }
 
 
-impl<A, P, T, C: Serialize> EditPage<A, P, T, C> {
+impl<A, P, T, C: Serialize, Cl> EditPage<A, P, T, C, Cl> {
     /// Required. Access token of the Telegraph account.
-    pub fn access_token(self, access_token: &str) -> EditPage<AccessToken, P, T, C> {
+    pub fn access_token(self, access_token: &str) -> EditPage<AccessToken, P, T, C, Cl> {
         EditPage { 
             client: self.client, 
             method_name: self.method_name,  
+            retry_policy: self.retry_policy,
             access_token: AccessToken(access_token.into()),  
             path: self.path, 
             title: self.title, 
             content: self.content, 
             author_name: self.author_name, 
             author_url: self.author_url, 
-            return_content: self.return_content 
+            return_content: self.return_content,
+            validate: self.validate
         }
     }
 
     /// Required. Path to the page.
-    pub fn title(self, title: &str) -> EditPage<A, P, Title, C> {
+    pub fn title(self, title: &str) -> EditPage<A, P, Title, C, Cl> {
         EditPage { 
             client: self.client, 
             method_name: self.method_name,  
+            retry_policy: self.retry_policy,
             access_token: self.access_token,  
             path: self.path, 
             title: Title(title.into()), 
             content: self.content, 
             author_name: self.author_name, 
             author_url: self.author_url, 
-            return_content: self.return_content 
+            return_content: self.return_content,
+            validate: self.validate
         }
     }
 
     /// Required. Page title.
-    pub fn path(self, path: &str) -> EditPage<A, Path, T, C> {
+    pub fn path(self, path: &str) -> EditPage<A, Path, T, C, Cl> {
         EditPage { 
             client: self.client, 
             method_name: self.method_name,  
+            retry_policy: self.retry_policy,
             access_token: self.access_token,  
             path: Path(path.into()), 
             title: self.title, 
             content: self.content, 
             author_name: self.author_name, 
             author_url: self.author_url, 
-            return_content: self.return_content 
+            return_content: self.return_content,
+            validate: self.validate
         }
     }
 
     /// Required. Content of the page.
-    pub fn content(self, content: Vec<Node>) -> EditPage<A, P, T, Content> {
+    pub fn content(self, content: Vec<Node>) -> EditPage<A, P, T, Content, Cl> {
         EditPage { 
             client: self.client, 
             method_name: self.method_name,  
+            retry_policy: self.retry_policy,
             access_token: self.access_token,  
             path: self.path, 
             title: self.title, 
             content: Content(content), 
             author_name: self.author_name, 
             author_url: self.author_url, 
-            return_content: self.return_content 
+            return_content: self.return_content,
+            validate: self.validate
         }
     }
 
@@ -120,17 +135,49 @@ impl<A, P, T, C: Serialize> EditPage<A, P, T, C> {
         self.return_content = return_content;
         self
     }
+
+    /// If `true`, the content is checked with [`validate`][crate::content::validate]
+    /// before sending and [`TelegraphError::InvalidContent`] is returned instead of the request.
+    pub fn validate(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
 }
 
 impl EditPage<AccessToken, Path, Title, Content> {
     /// Sending request to API
     pub fn send(self) -> Result<Page, TelegraphError> {
-        let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
-        let json: TelegraphResult<Page> = req.json()?;
-        if !json.ok {
-            Err(json.error.unwrap())
-        } else {
-            Ok(json.result.unwrap())
+        if self.validate {
+            self.content.validate()?;
+        }
+        self.retry_policy.run(|| {
+            let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
+            let json: TelegraphResult<Page> = req.json()?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl EditPage<AccessToken, Path, Title, Content, AsyncClient> {
+    /// Sending request to API
+    pub async fn send(self) -> Result<Page, TelegraphError> {
+        if self.validate {
+            self.content.validate()?;
         }
+        let this = &self;
+        self.retry_policy.run_async(|| async move {
+            let req = this.client.post(this.method_name.as_str()).form(this).send().await?;
+            let json: TelegraphResult<Page> = req.json().await?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        }).await
     }
 }
@@ -1,44 +1,51 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
 use serde::Serialize;
 
 
 use crate::error::TelegraphError;
+use crate::retry::RetryPolicy;
 use crate::types::{AccountField, TelegraphResult, Account};
 use crate::requests::{ApiFieldSerializer, NoAccessToken, AccessToken};
 
 
 /// Builder of `getAccountInfo`
-#[derive(Serialize)]
-pub struct GetAccountInfo<T> {
+#[derive(Clone, Serialize)]
+pub struct GetAccountInfo<T, Cl = Client> {
     #[serde(skip)]
-    client: Rc<Client>,
+    client: Arc<Cl>,
     #[serde(skip)]
-    method_name: Rc<String>,
+    method_name: Arc<String>,
+    #[serde(skip)]
+    retry_policy: RetryPolicy,
 
     access_token: T,
     #[serde(serialize_with = "ApiFieldSerializer::serialize")]
     fields: Option<Vec<AccountField>>
 }
 
-impl GetAccountInfo<NoAccessToken> {
-    pub(crate) fn new(client: Rc<Client>, method_name: Rc<String>) -> GetAccountInfo<NoAccessToken> {
+impl<Cl> GetAccountInfo<NoAccessToken, Cl> {
+    pub(crate) fn new(client: Arc<Cl>, method_name: Arc<String>, retry_policy: RetryPolicy) -> GetAccountInfo<NoAccessToken, Cl> {
         Self { 
             client, 
             method_name, 
+            retry_policy,
             access_token: NoAccessToken, 
             fields: vec![AccountField::ShortName, AccountField::AuthorName, AccountField::AuthorUrl].into()
         }
     }
 }
 
-impl<T> GetAccountInfo<T> {
+impl<T, Cl> GetAccountInfo<T, Cl> {
     /// Required. Access token of the Telegraph account.
-    pub fn access_token(self, access_token: &str) -> GetAccountInfo<AccessToken> {
+    pub fn access_token(self, access_token: &str) -> GetAccountInfo<AccessToken, Cl> {
         GetAccountInfo { 
             client: self.client, 
             method_name: self.method_name, 
+            retry_policy: self.retry_policy,
             access_token: AccessToken(access_token.into()), 
             fields: self.fields
         }
@@ -55,12 +62,31 @@ impl<T> GetAccountInfo<T> {
 impl GetAccountInfo<AccessToken> {
     /// Sending request to API
     pub fn send(self) -> Result<Account, TelegraphError> {
-        let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
-        let json: TelegraphResult<Account> = req.json()?;
-        if !json.ok {
-            Err(json.error.unwrap())
-        } else {
-            Ok(json.result.unwrap())
-        }
+        self.retry_policy.run(|| {
+            let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
+            let json: TelegraphResult<Account> = req.json()?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl GetAccountInfo<AccessToken, AsyncClient> {
+    /// Sending request to API
+    pub async fn send(self) -> Result<Account, TelegraphError> {
+        let this = &self;
+        self.retry_policy.run_async(|| async move {
+            let req = this.client.post(this.method_name.as_str()).form(this).send().await?;
+            let json: TelegraphResult<Account> = req.json().await?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        }).await
     }
 }
@@ -1,39 +1,45 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
 use serde::Serialize;
 
 use crate::types::{Page, TelegraphResult};
 use crate::error::TelegraphError;
+use crate::retry::RetryPolicy;
 use crate::requests::{NoPath, Path};
 
 
 /// Builder of `getPage`
-#[derive(Default, Serialize)]
-pub struct GetPage<P> {
+#[derive(Clone, Default, Serialize)]
+pub struct GetPage<P, Cl = Client> {
     #[serde(skip)]
-    client: Rc<Client>,
+    client: Arc<Cl>,
     #[serde(skip)]
-    method_name: Rc<String>,
+    method_name: Arc<String>,
+    #[serde(skip)]
+    retry_policy: RetryPolicy,
 
     path: P,
     return_content: bool
 }
 
-impl GetPage<NoPath> {
-    pub(crate) fn new(client: Rc<Client>, method_name: Rc<String>) -> GetPage<NoPath> {
-        Self { client, method_name, ..Self::default() }
+impl<Cl: Default> GetPage<NoPath, Cl> {
+    pub(crate) fn new(client: Arc<Cl>, method_name: Arc<String>, retry_policy: RetryPolicy) -> GetPage<NoPath, Cl> {
+        Self { client, method_name, retry_policy, ..Self::default() }
     }
 }
 
-impl<P> GetPage<P> {
+impl<P, Cl> GetPage<P, Cl> {
     /// Required. Path to the Telegraph page 
     /// (in the format Title-12-31, 
     /// i.e. everything that comes after <http://telegra.ph/>).
-    pub fn path(self, path: &str) -> GetPage<Path> {
+    pub fn path(self, path: &str) -> GetPage<Path, Cl> {
         GetPage { 
             client: self.client, 
             method_name: self.method_name, 
+            retry_policy: self.retry_policy,
             path: Path(path.into()), 
             return_content: self.return_content
         }
@@ -49,12 +55,31 @@ impl<P> GetPage<P> {
 impl GetPage<Path> {
     /// Sending request to API
     pub fn send(self) -> Result<Page, TelegraphError> {
-        let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
-        let json: TelegraphResult<Page> = req.json()?;
-        if !json.ok {
-            Err(json.error.unwrap())
-        } else {
-            Ok(json.result.unwrap())
-        }
+        self.retry_policy.run(|| {
+            let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
+            let json: TelegraphResult<Page> = req.json()?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl GetPage<Path, AsyncClient> {
+    /// Sending request to API
+    pub async fn send(self) -> Result<Page, TelegraphError> {
+        let this = &self;
+        self.retry_policy.run_async(|| async move {
+            let req = this.client.post(this.method_name.as_str()).form(this).send().await?;
+            let json: TelegraphResult<Page> = req.json().await?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        }).await
     }
 }
@@ -1,31 +1,37 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
 use serde::Serialize;
 
 use crate::types::{PageList, TelegraphResult};
 use crate::requests::{NoAccessToken, AccessToken};
 use crate::error::TelegraphError;
+use crate::retry::RetryPolicy;
 
 
 /// Builder of `getPageList`
-#[derive(Serialize)]
-pub struct GetPageList<T> {
+#[derive(Clone, Serialize)]
+pub struct GetPageList<T, Cl = Client> {
     #[serde(skip)]
-    client: Rc<Client>,
+    client: Arc<Cl>,
     #[serde(skip)]
-    method_name: Rc<String>,
+    method_name: Arc<String>,
+    #[serde(skip)]
+    retry_policy: RetryPolicy,
 
     access_token: T,
     offset: i32,
     limit: i32
 }
 
-impl GetPageList<NoAccessToken> {
-    pub(crate) fn new(client: Rc<Client>, method_name: Rc<String>) -> GetPageList<NoAccessToken> {
+impl<Cl> GetPageList<NoAccessToken, Cl> {
+    pub(crate) fn new(client: Arc<Cl>, method_name: Arc<String>, retry_policy: RetryPolicy) -> GetPageList<NoAccessToken, Cl> {
         Self { 
             client, 
             method_name, 
+            retry_policy,
             access_token: NoAccessToken,
             offset: 0,
             limit: 50 
@@ -33,12 +39,13 @@ impl GetPageList<NoAccessToken> {
     }
 }
 
-impl<T> GetPageList<T> {
+impl<T, Cl> GetPageList<T, Cl> {
     /// Required. Access token of the Telegraph account.
-    pub fn access_token(self, access_token: &str) -> GetPageList<AccessToken> {
+    pub fn access_token(self, access_token: &str) -> GetPageList<AccessToken, Cl> {
         GetPageList { 
             client: self.client, 
             method_name: self.method_name, 
+            retry_policy: self.retry_policy,
             access_token: AccessToken(access_token.into()), 
             offset: self.offset, 
             limit: self.limit 
@@ -61,12 +68,31 @@ impl<T> GetPageList<T> {
 impl GetPageList<AccessToken> {
     /// Sending request to API
     pub fn send(self) -> Result<PageList, TelegraphError> {
-        let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
-        let json: TelegraphResult<PageList> = req.json()?;
-        if !json.ok {
-            Err(json.error.unwrap())
-        } else {
-            Ok(json.result.unwrap())
-        }
+        self.retry_policy.run(|| {
+            let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
+            let json: TelegraphResult<PageList> = req.json()?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl GetPageList<AccessToken, AsyncClient> {
+    /// Sending request to API
+    pub async fn send(self) -> Result<PageList, TelegraphError> {
+        let this = &self;
+        self.retry_policy.run_async(|| async move {
+            let req = this.client.post(this.method_name.as_str()).form(this).send().await?;
+            let json: TelegraphResult<PageList> = req.json().await?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        }).await
     }
 }
@@ -1,20 +1,25 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
 use serde::Serialize;
 
 use crate::types::{TelegraphResult, PageViews};
 use crate::requests::{NoPath, Path};
 use crate::error::TelegraphError;
+use crate::retry::RetryPolicy;
 
 
 /// Builder of `getViews`
-#[derive(Default, Serialize)]
-pub struct GetViews<P> {
+#[derive(Clone, Default, Serialize)]
+pub struct GetViews<P, Cl = Client> {
     #[serde(skip)]
-    client: Rc<Client>,
+    client: Arc<Cl>,
     #[serde(skip)]
-    method_name: Rc<String>,
+    method_name: Arc<String>,
+    #[serde(skip)]
+    retry_policy: RetryPolicy,
 
     path: P,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -28,20 +33,21 @@ pub struct GetViews<P> {
 }
 
 
-impl GetViews<NoPath> {
-    pub(crate) fn new(client: Rc<Client>, method_name: Rc<String>) -> GetViews<NoPath> {
-        Self {  client, method_name, ..Self::default() }
+impl<Cl: Default> GetViews<NoPath, Cl> {
+    pub(crate) fn new(client: Arc<Cl>, method_name: Arc<String>, retry_policy: RetryPolicy) -> GetViews<NoPath, Cl> {
+        Self { client, method_name, retry_policy, ..Self::default() }
     }
 }
 
-impl<P> GetViews<P> {
+impl<P, Cl> GetViews<P, Cl> {
     /// Required. Path to the Telegraph page 
     /// (in the format Title-12-31, where 12 is the month 
     /// and 31 the day the article was first published).
-    pub fn path(self, path: &str) -> GetViews<Path> {
+    pub fn path(self, path: &str) -> GetViews<Path, Cl> {
         GetViews { 
             client: self.client, 
             method_name: self.method_name, 
+            retry_policy: self.retry_policy,
             path: Path(path.into()), 
             year: self.year, 
             month: self.month, 
@@ -84,12 +90,31 @@ impl<P> GetViews<P> {
 impl GetViews<Path> {
     /// Sending request to API
     pub fn send(self) -> Result<PageViews, TelegraphError> {
-        let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
-        let json: TelegraphResult<PageViews> = req.json()?;
-        if !json.ok {
-            Err(json.error.unwrap())
-        } else {
-            Ok(json.result.unwrap())
-        }
+        self.retry_policy.run(|| {
+            let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
+            let json: TelegraphResult<PageViews> = req.json()?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl GetViews<Path, AsyncClient> {
+    /// Sending request to API
+    pub async fn send(self) -> Result<PageViews, TelegraphError> {
+        let this = &self;
+        self.retry_policy.run_async(|| async move {
+            let req = this.client.post(this.method_name.as_str()).form(this).send().await?;
+            let json: TelegraphResult<PageViews> = req.json().await?;
+            if !json.ok {
+                Err(json.error.unwrap())
+            } else {
+                Ok(json.result.unwrap())
+            }
+        }).await
     }
 }
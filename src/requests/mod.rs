@@ -9,7 +9,8 @@
 //! 
 //! In case of an unsuccessful request, `ok` equals `false`, 
 //! and the `error` is explained in the error 
-//! [`TelegraphError::ApiError`][crate::error::TelegraphError] (e.g. SHORT_NAME_REQUIRED).
+//! [`TelegraphError::ApiError`][crate::error::TelegraphError] as [`ApiErrorKind`][crate::error::ApiErrorKind]
+//! (e.g. [`ShortNameRequired`][crate::error::ApiErrorKind::ShortNameRequired]).
 
 
 
@@ -24,6 +25,9 @@ mod get_page;
 mod get_page_list;
 mod get_views;
 
+mod pages;
+mod views_range;
+
 use serde::{Serialize, Serializer};
 use serde::ser;
 
@@ -38,39 +42,51 @@ pub use get_page::GetPage;
 pub use get_page_list::GetPageList;
 pub use get_views::GetViews;
 
+pub use pages::Pages;
+pub use views_range::{Granularity, ViewsDate, ViewsRangeIssue, MAX_VIEWS_BUCKETS};
+pub(crate) use views_range::ViewsRange;
+
+use crate::content::validate;
+use crate::error::TelegraphError;
 use crate::types::Node;
 
 
 /// Access token of the empty state type
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct NoAccessToken;
 
 /// Access token of the filled state type
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct AccessToken(String);
 
 /// Title of the empty state type 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct NoTitle;
 
 /// Title of the filled state type
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct Title(String);
 
 /// Title of the empty state type 
-#[derive(Default, Serialize)]
+#[derive(Clone, Default, Serialize)]
 pub struct NoContent;
 
 /// Title of the filled state type
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct Content(Vec<Node>);
 
+impl Content {
+    fn validate(&self) -> Result<(), TelegraphError> {
+        validate(&self.0).map_err(TelegraphError::InvalidContent)
+    }
+}
+
 /// Path of the empty state type
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct NoPath;
 
 /// Path of the filled state type
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct Path(String);
 
 /// Custom serializer for method bulders
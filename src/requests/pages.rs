@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
+
+use crate::error::TelegraphError;
+use crate::requests::{AccessToken, GetPageList};
+use crate::retry::RetryPolicy;
+use crate::types::{Page, PageList};
+
+
+/// Maximum `limit` of `getPageList`
+const MAX_BATCH_SIZE: u32 = 200;
+
+
+/// Iterator over all pages of an account
+///
+/// Fetches successive [`PageList`] batches with `getPageList` lazily,
+/// until `total_count` pages are returned.
+/// Stops after the first error.
+pub struct Pages<Cl = Client> {
+    client: Arc<Cl>,
+    method_name: Arc<String>,
+    retry_policy: RetryPolicy,
+
+    access_token: String,
+    batch_size: u32,
+    offset: u32,
+    total_count: Option<u32>,
+    done: bool,
+    buffer: VecDeque<Page>
+}
+
+
+impl<Cl> Pages<Cl> {
+    pub(crate) fn new(client: Arc<Cl>, method_name: Arc<String>, retry_policy: RetryPolicy, access_token: &str) -> Self {
+        Pages {
+            client,
+            method_name,
+            retry_policy,
+            access_token: access_token.into(),
+            batch_size: MAX_BATCH_SIZE,
+            offset: 0,
+            total_count: None,
+            done: false,
+            buffer: VecDeque::new()
+        }
+    }
+
+    /// Number of pages fetched by one request, from 1 to 200 (default).
+    pub fn batch_size(mut self, batch_size: u32) -> Self {
+        self.batch_size = batch_size.clamp(1, MAX_BATCH_SIZE);
+        self
+    }
+
+    /// Total number of pages of the account, known after the first request.
+    pub fn total_count(&self) -> Option<u32> {
+        self.total_count
+    }
+
+    fn request(&self) -> Option<GetPageList<AccessToken, Cl>> {
+        if self.done || self.total_count.is_some_and(|total_count| self.offset >= total_count) {
+            return None;
+        }
+        let request = GetPageList::new(self.client.clone(), self.method_name.clone(), self.retry_policy)
+            .access_token(&self.access_token)
+            .offset(self.offset as i32)
+            .limit(self.batch_size as i32);
+        Some(request)
+    }
+
+    fn receive(&mut self, result: Result<PageList, TelegraphError>) -> Result<(), TelegraphError> {
+        match result {
+            Ok(page_list) => {
+                self.total_count = Some(page_list.total_count);
+                self.done = page_list.pages.is_empty();
+                self.offset += page_list.pages.len() as u32;
+                self.buffer.extend(page_list.pages);
+                Ok(())
+            },
+            Err(error) => {
+                self.done = true;
+                Err(error)
+            }
+        }
+    }
+}
+
+
+impl Iterator for Pages {
+    type Item = Result<Page, TelegraphError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(page) = self.buffer.pop_front() {
+                return Some(Ok(page));
+            }
+            let request = self.request()?;
+            if let Err(error) = self.receive(request.send()) {
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+
+#[cfg(feature = "async")]
+impl Pages<AsyncClient> {
+    /// Next page of the account, `None` when all pages are returned.
+    pub async fn next_page(&mut self) -> Option<Result<Page, TelegraphError>> {
+        loop {
+            if let Some(page) = self.buffer.pop_front() {
+                return Some(Ok(page));
+            }
+            let request = self.request()?;
+            if let Err(error) = self.receive(request.send().await) {
+                return Some(Err(error));
+            }
+        }
+    }
+
+    /// Converts into a [`Stream`][futures_util::Stream] of pages.
+    pub fn into_stream(self) -> impl futures_util::Stream<Item = Result<Page, TelegraphError>> {
+        futures_util::stream::unfold(self, |mut pages| async move {
+            pages.next_page().await.map(|page| (page, pages))
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+    use crate::error::ApiErrorKind;
+    use crate::testing::FakeTelegraph;
+    use crate::TelegraphError;
+
+    #[test]
+    fn iterate_pages() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let token = telegraph.create_account().short_name("Pages").send().unwrap().access_token.unwrap();
+        let content = build_content(r#"[{"tag": "p", "children": ["Hello"]}]"#).unwrap();
+        for index in 0..5 {
+            telegraph.create_page()
+                .access_token(&token)
+                .title(&format!("Page {}", index))
+                .content(content.clone())
+                .send()
+                .unwrap();
+        }
+
+        let mut pages = telegraph.pages(&token).batch_size(2);
+        assert_eq!(pages.total_count(), None);
+        let titles = pages.by_ref().map(|page| page.unwrap().title).collect::<Vec<String>>();
+        assert_eq!(titles.len(), 5);
+        assert!(titles.contains(&"Page 0".to_string()) && titles.contains(&"Page 4".to_string()));
+        assert_eq!(pages.total_count(), Some(5));
+
+        let mut pages = telegraph.pages("invalid");
+        assert!(matches!(pages.next(), Some(Err(TelegraphError::ApiError(ApiErrorKind::AccessTokenInvalid)))));
+        assert!(pages.next().is_none());
+    }
+}
@@ -1,36 +1,46 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
 use serde::Serialize;
 
 use crate::types::{Account, TelegraphResult};
 use crate::requests::{NoAccessToken, AccessToken};
 use crate::error::TelegraphError;
+use crate::store::AccountStore;
 
 
 /// Builder of `revokeAccessToken`
-#[derive(Default, Serialize)]
-pub struct RevokeAccessToken<T> {
+#[derive(Clone, Default, Serialize)]
+pub struct RevokeAccessToken<T, Cl = Client> {
     #[serde(skip)]
-    client: Rc<Client>,
+    client: Arc<Cl>,
     #[serde(skip)]
-    method_name: Rc<String>,
+    method_name: Arc<String>,
+    #[serde(skip)]
+    account_store: Option<AccountStore>,
 
     access_token: T
 }
 
-impl RevokeAccessToken<NoAccessToken> {
-    pub(crate) fn new(client: Rc<Client>, method_name: Rc<String>) -> RevokeAccessToken<NoAccessToken> {
-        Self {  client, method_name, ..Self::default() }
+impl<Cl: Default> RevokeAccessToken<NoAccessToken, Cl> {
+    pub(crate) fn new(
+        client: Arc<Cl>,
+        method_name: Arc<String>,
+        account_store: Option<AccountStore>
+    ) -> RevokeAccessToken<NoAccessToken, Cl> {
+        Self { client, method_name, account_store, ..Self::default() }
     }
 }
 
-impl<T> RevokeAccessToken<T> {
+impl<T, Cl> RevokeAccessToken<T, Cl> {
     /// Required. Access token of the Telegraph account.
-    pub fn access_token(self, access_token: &str) -> RevokeAccessToken<AccessToken> {
+    pub fn access_token(self, access_token: &str) -> RevokeAccessToken<AccessToken, Cl> {
         RevokeAccessToken {
             client: self.client,
             method_name: self.method_name,
+            account_store: self.account_store,
             access_token: AccessToken(access_token.into())
         }
     }
@@ -38,13 +48,44 @@ impl<T> RevokeAccessToken<T> {
 
 impl RevokeAccessToken<AccessToken> {
     /// Sending request to API
+    ///
+    /// If saving the account to the store fails, the account is returned
+    /// in [`TelegraphError::AccountNotSaved`].
     pub fn send(self) -> Result<Account, TelegraphError> {
         let req = self.client.post(self.method_name.as_str()).form(&self).send()?;
         let json: TelegraphResult<Account> = req.json()?;
         if !json.ok {
-            Err(json.error.unwrap())
-        } else {
-            Ok(json.result.unwrap())
+            return Err(json.error.unwrap());
+        }
+        let account = json.result.unwrap();
+        if let Some(store) = &self.account_store {
+            if let Err(error) = store.rotate(&self.access_token.0, &account) {
+                return Err(TelegraphError::AccountNotSaved(Box::new(account), Box::new(error)));
+            }
+        }
+        Ok(account)
+    }
+}
+
+#[cfg(feature = "async")]
+impl RevokeAccessToken<AccessToken, AsyncClient> {
+    /// Sending request to API
+    ///
+    /// If saving the account to the store fails, the account is returned
+    /// in [`TelegraphError::AccountNotSaved`].
+    pub async fn send(self) -> Result<Account, TelegraphError> {
+        let req = self.client.post(self.method_name.as_str()).form(&self).send().await?;
+        let json: TelegraphResult<Account> = req.json().await?;
+        if !json.ok {
+            return Err(json.error.unwrap());
+        }
+        let account = json.result.unwrap();
+        match &self.account_store {
+            Some(store) => {
+                let access_token = self.access_token.0.clone();
+                store.write_blocking(account, move |store, account| store.rotate(&access_token, account)).await
+            },
+            None => Ok(account)
         }
     }
 }
@@ -0,0 +1,280 @@
+use std::fmt;
+use std::sync::Arc;
+
+use reqwest::blocking::Client;
+#[cfg(feature = "async")]
+use reqwest::Client as AsyncClient;
+
+use crate::error::TelegraphError;
+use crate::requests::{GetViews, Path};
+use crate::retry::RetryPolicy;
+
+
+/// Maximum number of buckets, and `getViews` requests, of a views series
+pub const MAX_VIEWS_BUCKETS: usize = 1000;
+
+
+/// Size of the buckets of a views series
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Granularity {
+    /// One point per hour
+    Hour,
+    /// One point per day
+    Day,
+    /// One point per month
+    Month
+}
+
+
+/// Date and hour of a views bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ViewsDate {
+    /// Year, 2000-2100
+    pub year: i32,
+    /// Month, 1-12
+    pub month: i32,
+    /// Day of the month, 1-31
+    pub day: i32,
+    /// Hour, 0-23
+    pub hour: i32
+}
+
+
+impl ViewsDate {
+    /// Constructs the first hour of the day.
+    pub fn new(year: i32, month: i32, day: i32) -> Self {
+        ViewsDate { year, month, day, hour: 0 }
+    }
+
+    /// Sets the hour.
+    pub fn hour(mut self, hour: i32) -> Self {
+        self.hour = hour;
+        self
+    }
+
+    fn is_valid(&self) -> bool {
+        (2000..=2100).contains(&self.year)
+            && (1..=12).contains(&self.month)
+            && (1..=days_in_month(self.year, self.month)).contains(&self.day)
+            && (0..24).contains(&self.hour)
+    }
+
+    /// Start of the bucket containing the date.
+    fn truncate(self, granularity: Granularity) -> Self {
+        match granularity {
+            Granularity::Hour => self,
+            Granularity::Day => ViewsDate { hour: 0, ..self },
+            Granularity::Month => ViewsDate { day: 1, hour: 0, ..self }
+        }
+    }
+
+    /// Start of the next bucket.
+    fn next(self, granularity: Granularity) -> Self {
+        let mut next = self;
+        if granularity == Granularity::Hour {
+            next.hour += 1;
+            if next.hour < 24 {
+                return next;
+            }
+            next.hour = 0;
+        }
+        if granularity != Granularity::Month {
+            next.day += 1;
+            if next.day <= days_in_month(next.year, next.month) {
+                return next;
+            }
+        }
+        next.day = 1;
+        next.month += 1;
+        if next.month > 12 {
+            next.month = 1;
+            next.year += 1;
+        }
+        next
+    }
+}
+
+
+impl fmt::Display for ViewsDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}T{:02}:00", self.year, self.month, self.day, self.hour)
+    }
+}
+
+
+/// Views range rejected before sending requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViewsRangeIssue {
+    /// Date out of the range accepted by `getViews` or not in the calendar
+    InvalidDate(ViewsDate),
+    /// End of the range is before its start
+    Reversed {
+        /// Start of the range
+        from: ViewsDate,
+        /// End of the range
+        to: ViewsDate
+    },
+    /// Range has more than [`MAX_VIEWS_BUCKETS`] buckets
+    TooManyBuckets
+}
+
+
+impl fmt::Display for ViewsRangeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViewsRangeIssue::InvalidDate(date) => write!(f, "invalid date {}", date),
+            ViewsRangeIssue::Reversed { from, to } => write!(f, "range end {} is before its start {}", to, from),
+            ViewsRangeIssue::TooManyBuckets => write!(f, "range has more than {} buckets", MAX_VIEWS_BUCKETS)
+        }
+    }
+}
+
+
+fn days_in_month(year: i32, month: i32) -> i32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+
+/// Requests of a views series, one `getViews` per bucket
+pub(crate) struct ViewsRange<Cl> {
+    client: Arc<Cl>,
+    method_name: Arc<String>,
+    retry_policy: RetryPolicy,
+
+    path: String,
+    buckets: Vec<ViewsDate>,
+    granularity: Granularity
+}
+
+
+impl<Cl: Default> ViewsRange<Cl> {
+    pub(crate) fn new(
+        client: Arc<Cl>,
+        method_name: Arc<String>,
+        retry_policy: RetryPolicy,
+        path: &str,
+        from: ViewsDate,
+        to: ViewsDate,
+        granularity: Granularity
+    ) -> Result<Self, TelegraphError> {
+        let invalid = |issue| Err(TelegraphError::InvalidViewsRange(issue));
+        if let Some(&date) = [from, to].iter().find(|date| !date.is_valid()) {
+            return invalid(ViewsRangeIssue::InvalidDate(date));
+        }
+        if to < from {
+            return invalid(ViewsRangeIssue::Reversed { from, to });
+        }
+        let mut buckets = vec![];
+        let mut date = from.truncate(granularity);
+        while date <= to {
+            if buckets.len() == MAX_VIEWS_BUCKETS {
+                return invalid(ViewsRangeIssue::TooManyBuckets);
+            }
+            buckets.push(date);
+            date = date.next(granularity);
+        }
+        Ok(ViewsRange { client, method_name, retry_policy, path: path.into(), buckets, granularity })
+    }
+
+    fn request(&self, date: ViewsDate) -> GetViews<Path, Cl> {
+        let request = GetViews::new(self.client.clone(), self.method_name.clone(), self.retry_policy)
+            .path(&self.path)
+            .year(date.year)
+            .month(date.month);
+        match self.granularity {
+            Granularity::Hour => request.day(date.day).hour(date.hour),
+            Granularity::Day => request.day(date.day),
+            Granularity::Month => request
+        }
+    }
+}
+
+
+impl ViewsRange<Client> {
+    pub(crate) fn send(self) -> Result<Vec<(ViewsDate, u32)>, TelegraphError> {
+        self.buckets.iter()
+            .map(|&date| self.request(date).send().map(|views| (date, views.views)))
+            .collect()
+    }
+}
+
+
+#[cfg(feature = "async")]
+impl ViewsRange<AsyncClient> {
+    pub(crate) async fn send(self) -> Result<Vec<(ViewsDate, u32)>, TelegraphError> {
+        let mut series = Vec::with_capacity(self.buckets.len());
+        for &date in &self.buckets {
+            series.push((date, self.request(date).send().await?.views));
+        }
+        Ok(series)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+    use crate::testing::FakeTelegraph;
+    use crate::TelegraphError;
+
+    use super::{Granularity, ViewsDate, ViewsRangeIssue};
+
+    #[test]
+    fn views_dates() {
+        let date = ViewsDate::new(2024, 2, 28).hour(23);
+        assert_eq!(date.next(Granularity::Hour), ViewsDate::new(2024, 2, 29));
+        assert_eq!(date.next(Granularity::Day), ViewsDate::new(2024, 2, 29).hour(23));
+        assert_eq!(ViewsDate::new(2023, 2, 28).next(Granularity::Day), ViewsDate::new(2023, 3, 1));
+        assert_eq!(ViewsDate::new(2023, 12, 31).next(Granularity::Day), ViewsDate::new(2024, 1, 1));
+        assert_eq!(date.truncate(Granularity::Month).next(Granularity::Month), ViewsDate::new(2024, 3, 1));
+        assert_eq!(date.to_string(), "2024-02-28T23:00");
+        assert!(!ViewsDate::new(2023, 2, 29).is_valid());
+        assert!(!ViewsDate::new(1999, 12, 31).is_valid());
+    }
+
+    #[test]
+    fn views_range() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let token = telegraph.create_account().short_name("Views").send().unwrap().access_token.unwrap();
+        let content = build_content(r#"[{"tag": "p", "children": ["Hello"]}]"#).unwrap();
+        let path = telegraph.create_page()
+            .access_token(&token)
+            .title("Views")
+            .content(content)
+            .send()
+            .unwrap()
+            .path;
+        server.add_views(&path, 2024, 1, 31, 22, 3);
+        server.add_views(&path, 2024, 1, 31, 23, 4);
+        server.add_views(&path, 2024, 2, 1, 0, 5);
+
+        let daily = telegraph.views_range(&path, ViewsDate::new(2024, 1, 30), ViewsDate::new(2024, 2, 1), Granularity::Day).unwrap();
+        assert_eq!(daily, vec![
+            (ViewsDate::new(2024, 1, 30), 0),
+            (ViewsDate::new(2024, 1, 31), 7),
+            (ViewsDate::new(2024, 2, 1), 5)
+        ]);
+
+        let hourly = telegraph.views_range(&path, ViewsDate::new(2024, 1, 31).hour(23), ViewsDate::new(2024, 2, 1), Granularity::Hour).unwrap();
+        assert_eq!(hourly, vec![(ViewsDate::new(2024, 1, 31).hour(23), 4), (ViewsDate::new(2024, 2, 1), 5)]);
+
+        let monthly = telegraph.views_range(&path, ViewsDate::new(2024, 1, 15), ViewsDate::new(2024, 2, 10), Granularity::Month).unwrap();
+        assert_eq!(monthly, vec![(ViewsDate::new(2024, 1, 1), 7), (ViewsDate::new(2024, 2, 1), 5)]);
+
+        let range = |from, to, granularity| match telegraph.views_range(&path, from, to, granularity) {
+            Err(TelegraphError::InvalidViewsRange(issue)) => issue,
+            result => panic!("range must be rejected: {:?}", result)
+        };
+        let (day, next_day) = (ViewsDate::new(2024, 2, 1), ViewsDate::new(2024, 2, 2));
+        assert_eq!(range(ViewsDate::new(2024, 2, 30), next_day, Granularity::Day), ViewsRangeIssue::InvalidDate(ViewsDate::new(2024, 2, 30)));
+        assert_eq!(range(day, ViewsDate::new(2101, 1, 1), Granularity::Month), ViewsRangeIssue::InvalidDate(ViewsDate::new(2101, 1, 1)));
+        assert_eq!(range(next_day, day, Granularity::Day), ViewsRangeIssue::Reversed { from: next_day, to: day });
+        assert_eq!(range(day, ViewsDate::new(2026, 1, 1), Granularity::Hour), ViewsRangeIssue::TooManyBuckets);
+    }
+}
@@ -0,0 +1,227 @@
+//! Retrying failed requests
+//!
+//! [`RetryPolicy`] is configured with [`TelegraphBuilder::retry_policy`][crate::TelegraphBuilder::retry_policy]
+//! and repeats requests failed with `FLOOD_WAIT_<n>`, `PAGE_SAVE_FAILED`,
+//! timeouts or connection errors.
+//!
+//! Only idempotent methods (`editAccountInfo`, `getAccountInfo`, `editPage`,
+//! `getPage`, `getPageList`, `getViews`) are retried by default.
+//! `createPage` is retried after opting in with
+//! [`CreatePage::retry`][crate::requests::CreatePage::retry],
+//! `createAccount` and `revokeAccessToken` are never retried.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+#[cfg(feature = "async")]
+use std::future::Future;
+
+use crate::TelegraphError;
+
+
+/// Policy of retrying failed requests
+///
+/// Waits `base_delay * 2^(attempt - 1)` (up to `max_delay`) between attempts,
+/// randomized between half and full value when jitter is enabled.
+/// `FLOOD_WAIT_<n>` waits exactly `n` seconds, or fails immediately
+/// if `n` is longer than `max_flood_wait`.
+///
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use telegraph_api_rs::{Telegraph, RetryPolicy};
+///
+/// let telegraph = Telegraph::builder()
+/// .retry_policy(RetryPolicy::default().max_attempts(5).base_delay(Duration::from_millis(200)))
+/// .build();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    max_flood_wait: Duration,
+    jitter: bool
+}
+
+
+impl Default for RetryPolicy {
+    /// 3 attempts, 500 ms base delay, 30 s max delay,
+    /// flood wait up to 60 s, jitter enabled.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_flood_wait: Duration::from_secs(60),
+            jitter: true
+        }
+    }
+}
+
+
+impl RetryPolicy {
+    /// Policy without retries, used by default.
+    pub fn none() -> Self {
+        RetryPolicy { max_attempts: 1, ..Self::default() }
+    }
+
+    /// Maximum number of attempts including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Delay before the second attempt.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Maximum delay between attempts.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Longest `FLOOD_WAIT_<n>` to wait for.
+    pub fn max_flood_wait(mut self, max_flood_wait: Duration) -> Self {
+        self.max_flood_wait = max_flood_wait;
+        self
+    }
+
+    /// Randomize backoff delays.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Delay before repeating the request failed on `attempt` (starting from 1)
+    /// with `error`, `None` if the request must not be repeated.
+    pub fn delay(&self, attempt: u32, error: &TelegraphError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !error.is_retryable() {
+            return None;
+        }
+        if let Some(seconds) = error.api_error().and_then(|kind| kind.flood_wait()) {
+            let wait = Duration::from_secs(seconds);
+            return if wait <= self.max_flood_wait { Some(wait) } else { None };
+        }
+
+        let factor = 2u32.saturating_pow(attempt - 1);
+        let backoff = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        if !self.jitter {
+            return Some(backoff);
+        }
+        let half = backoff / 2;
+        let nanos = u64::try_from(half.as_nanos()).unwrap_or(u64::MAX);
+        Some(half + Duration::from_nanos(random() % nanos.saturating_add(1)))
+    }
+
+    pub(crate) fn run<T, F>(&self, mut send: F) -> Result<T, TelegraphError>
+    where F: FnMut() -> Result<T, TelegraphError>
+    {
+        let mut attempt = 1;
+        loop {
+            match send() {
+                Err(error) => match self.delay(attempt, &error) {
+                    Some(delay) => std::thread::sleep(delay),
+                    None => return Err(error)
+                },
+                result => return result
+            }
+            attempt += 1;
+        }
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) async fn run_async<T, F, Fut>(&self, mut send: F) -> Result<T, TelegraphError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, TelegraphError>>
+    {
+        let mut attempt = 1;
+        loop {
+            match send().await {
+                Err(error) => match self.delay(attempt, &error) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(error)
+                },
+                result => return result
+            }
+            attempt += 1;
+        }
+    }
+}
+
+
+fn random() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos());
+    hasher.finish()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::error::ApiErrorKind;
+    use crate::testing::FakeTelegraph;
+    use crate::{build_content, Telegraph, TelegraphError};
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn retry_delays() {
+        let policy = RetryPolicy::default().jitter(false).max_delay(Duration::from_secs(1));
+        let flood = TelegraphError::ApiError(ApiErrorKind::FloodWait(5));
+        let save = TelegraphError::ApiError(ApiErrorKind::PageSaveFailed);
+        let title = TelegraphError::ApiError(ApiErrorKind::TitleRequired);
+
+        assert_eq!(policy.delay(1, &save), Some(Duration::from_millis(500)));
+        assert_eq!(policy.max_attempts(5).delay(3, &save), Some(Duration::from_secs(1)));
+        assert_eq!(policy.delay(3, &save), None);
+        assert_eq!(policy.delay(1, &flood), Some(Duration::from_secs(5)));
+        assert_eq!(policy.max_flood_wait(Duration::from_secs(1)).delay(1, &flood), None);
+        assert_eq!(policy.delay(1, &title), None);
+        assert_eq!(RetryPolicy::none().delay(1, &save), None);
+
+        let jittered = RetryPolicy::default().delay(2, &save).unwrap();
+        assert!(jittered >= Duration::from_millis(500) && jittered <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn retry_requests() {
+        let server = FakeTelegraph::start();
+        let telegraph = Telegraph::builder()
+            .api_base(server.url())
+            .retry_policy(RetryPolicy::default().base_delay(Duration::from_millis(1)))
+            .build();
+        let token = telegraph.create_account().short_name("Retry").send().unwrap().access_token.unwrap();
+        let content = build_content(r#"[{"tag": "p", "children": ["Hello"]}]"#).unwrap();
+
+        server.fail_next("FLOOD_WAIT_0", 1);
+        let error = telegraph.create_page()
+            .access_token(&token)
+            .title("Title")
+            .content(content.clone())
+            .send()
+            .unwrap_err();
+        assert!(matches!(error, TelegraphError::ApiError(ApiErrorKind::FloodWait(0))));
+
+        server.fail_next("PAGE_SAVE_FAILED", 2);
+        let page = telegraph.create_page()
+            .access_token(&token)
+            .title("Title")
+            .content(content)
+            .retry(true)
+            .send()
+            .unwrap();
+
+        server.fail_next("FLOOD_WAIT_0", 2);
+        assert_eq!(telegraph.get_page().path(&page.path).send().unwrap().title, "Title");
+
+        server.fail_next("FLOOD_WAIT_0", 3);
+        assert!(telegraph.get_page().path(&page.path).send().is_err());
+    }
+}
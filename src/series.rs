@@ -0,0 +1,261 @@
+//! Publishing content too big for one page as a series of linked pages
+//!
+//! [`PageSeries`] splits the content with [`split_content`] into parts
+//! under [`MAX_CONTENT_SIZE`], creates a page for every part, titled
+//! `Title (1/3)`, then edits the parts to add "Previous" and "Next" links
+//! at the bottom. An index page with the links to all parts is created on request.
+//! If a request fails midway, the pages already created are returned in [`IncompleteSeries`].
+
+use crate::content::{split_content, MAX_CONTENT_SIZE};
+use crate::types::{Node, Page};
+use crate::{Telegraph, TelegraphError};
+
+
+/// Size kept free in every part for the navigation links
+pub const NAVIGATION_RESERVE: usize = 2048;
+
+
+/// Pages created by [`PageSeries::send`]
+#[derive(Debug)]
+pub struct PublishedSeries {
+    /// Parts in the reading order
+    pub parts: Vec<Page>,
+    /// Index page, if requested
+    pub index: Option<Page>
+}
+
+
+/// Pages created by a [`PageSeries::send`] that failed,
+/// returned in [`TelegraphError::IncompleteSeries`]
+///
+/// Telegraph can not delete pages, so the created pages are kept
+/// to be reused or fixed with `editPage`.
+#[derive(Debug)]
+pub struct IncompleteSeries {
+    /// Parts created before the failure and the index page, if created
+    pub series: PublishedSeries,
+    /// Number of parts of the series
+    pub count: usize,
+    /// Number of the first parts edited to add the navigation links
+    pub linked: usize,
+    /// Error of the failed request
+    pub error: TelegraphError
+}
+
+
+/// Publisher of a page series, see [`Telegraph::create_page_series`]
+#[derive(Clone)]
+pub struct PageSeries {
+    telegraph: Telegraph,
+    access_token: String,
+    title: String,
+    content: Vec<Node>,
+    max_size: usize,
+    index: bool,
+    author_name: Option<String>,
+    author_url: Option<String>
+}
+
+
+impl PageSeries {
+    pub(crate) fn new(telegraph: Telegraph, access_token: &str, title: &str, content: Vec<Node>) -> Self {
+        PageSeries {
+            telegraph,
+            access_token: access_token.into(),
+            title: title.into(),
+            content,
+            max_size: MAX_CONTENT_SIZE,
+            index: false,
+            author_name: None,
+            author_url: None
+        }
+    }
+
+    /// Maximum serialized size of a page content, [`MAX_CONTENT_SIZE`] by default.
+    /// Every part is split to [`NAVIGATION_RESERVE`] bytes less.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// If `true`, an index page titled with the series title links to all parts,
+    /// and every part links back to it.
+    pub fn index(mut self, index: bool) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Author name of the pages.
+    pub fn author_name(mut self, author_name: &str) -> Self {
+        self.author_name = Some(author_name.into());
+        self
+    }
+
+    /// Profile link of the author of the pages.
+    pub fn author_url(mut self, author_url: &str) -> Self {
+        self.author_url = Some(author_url.into());
+        self
+    }
+
+    /// Creates the pages of the series.
+    ///
+    /// Content fitting in one page is created as a single page without links or index.
+    /// A request failing after the first part is created returns
+    /// [`TelegraphError::IncompleteSeries`] with the pages already created.
+    pub fn send(self) -> Result<PublishedSeries, TelegraphError> {
+        let mut parts = split_content(self.content.clone(), self.max_size.saturating_sub(NAVIGATION_RESERVE))?;
+        if parts.len() <= 1 {
+            let page = self.create(&self.title, parts.pop().unwrap_or_default())?;
+            return Ok(PublishedSeries { parts: vec![page], index: None });
+        }
+
+        let count = parts.len();
+        let mut series = PublishedSeries { parts: Vec::with_capacity(count), index: None };
+        for (number, part) in parts.iter().enumerate() {
+            match self.create(&part_title(&self.title, number, count), part.clone()) {
+                Ok(page) => series.parts.push(page),
+                Err(error) if series.parts.is_empty() => return Err(error),
+                Err(error) => return Err(incomplete(series, count, 0, error))
+            }
+        }
+        if self.index {
+            let links = series.parts.iter().map(|page| Node::li().child(Node::a(&page.url).text(&page.title)));
+            match self.create(&self.title, vec![Node::ol().children(links).into()]) {
+                Ok(index) => series.index = Some(index),
+                Err(error) => return Err(incomplete(series, count, 0, error))
+            }
+        }
+
+        for (number, mut part) in parts.into_iter().enumerate() {
+            let previous = number.checked_sub(1).map(|previous| series.parts[previous].url.as_str());
+            let next = series.parts.get(number + 1).map(|next| next.url.as_str());
+            part.push(navigation(previous, series.index.as_ref().map(|index| index.url.as_str()), next));
+            match self.edit(&series.parts[number], part) {
+                Ok(page) => series.parts[number] = page,
+                Err(error) => return Err(incomplete(series, count, number, error))
+            }
+        }
+        Ok(series)
+    }
+
+    fn create(&self, title: &str, content: Vec<Node>) -> Result<Page, TelegraphError> {
+        let mut request = self.telegraph.create_page()
+            .access_token(&self.access_token)
+            .title(title)
+            .content(content);
+        if let Some(author_name) = &self.author_name {
+            request = request.author_name(author_name);
+        }
+        if let Some(author_url) = &self.author_url {
+            request = request.author_url(author_url);
+        }
+        request.send()
+    }
+
+    fn edit(&self, page: &Page, content: Vec<Node>) -> Result<Page, TelegraphError> {
+        let mut request = self.telegraph.edit_page()
+            .access_token(&self.access_token)
+            .path(&page.path)
+            .title(&page.title)
+            .content(content);
+        if let Some(author_name) = &self.author_name {
+            request = request.author_name(author_name);
+        }
+        if let Some(author_url) = &self.author_url {
+            request = request.author_url(author_url);
+        }
+        request.send()
+    }
+}
+
+
+fn incomplete(series: PublishedSeries, count: usize, linked: usize, error: TelegraphError) -> TelegraphError {
+    TelegraphError::IncompleteSeries(Box::new(IncompleteSeries { series, count, linked, error }))
+}
+
+
+fn part_title(title: &str, number: usize, count: usize) -> String {
+    format!("{} ({}/{})", title, number + 1, count)
+}
+
+
+/// Paragraph with the links to the previous part, the index and the next part.
+fn navigation(previous: Option<&str>, index: Option<&str>, next: Option<&str>) -> Node {
+    let links = [("Previous", previous), ("Contents", index), ("Next", next)];
+    let mut paragraph = Node::p();
+    for (text, url) in links.into_iter().filter_map(|(text, url)| Some((text, url?))) {
+        if paragraph.children.as_ref().is_some_and(|children| !children.is_empty()) {
+            paragraph = paragraph.text(" | ");
+        }
+        paragraph = paragraph.child(Node::a(url).text(text));
+    }
+    paragraph.into()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::content::to_html;
+    use crate::error::ApiErrorKind;
+    use crate::testing::FakeTelegraph;
+    use crate::types::Node;
+    use crate::TelegraphError;
+
+    use super::NAVIGATION_RESERVE;
+
+    #[test]
+    fn publish_series() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let token = telegraph.create_account().short_name("Report").send().unwrap().access_token.unwrap();
+        let content: Vec<Node> = (0..6).map(|index| Node::p().text(&format!("{} {}", index, "text ".repeat(100))).into()).collect();
+
+        let series = telegraph.create_page_series(&token, "Report", content.clone())
+            .max_size(NAVIGATION_RESERVE + 1200)
+            .index(true)
+            .send()
+            .unwrap();
+        assert_eq!(series.parts.len(), 3);
+        let index = series.index.unwrap();
+        assert_eq!(index.title, "Report");
+
+        let titles: Vec<_> = series.parts.iter().map(|page| page.title.as_str()).collect();
+        assert_eq!(titles, vec!["Report (1/3)", "Report (2/3)", "Report (3/3)"]);
+        let urls: Vec<_> = series.parts.iter().map(|page| page.url.as_str()).collect();
+        let html = |path: &str| to_html(&telegraph.get_page().path(path).return_content(true).send().unwrap().content.unwrap());
+        let middle = html(&series.parts[1].path);
+        assert!(middle.starts_with(&to_html(&content[2..4])));
+        assert!(middle.ends_with(&format!(
+            r#"<p><a href="{}">Previous</a> | <a href="{}">Contents</a> | <a href="{}">Next</a></p>"#,
+            urls[0], index.url, urls[2]
+        )));
+        assert!(html(&series.parts[0].path).ends_with(&format!(r#"<a href="{}">Next</a></p>"#, urls[1])));
+        assert!(html(&index.path).contains(&format!(r#"<li><a href="{}">Report (3/3)</a></li>"#, urls[2])));
+
+        let single = telegraph.create_page_series(&token, "Short", content[..1].to_vec()).index(true).send().unwrap();
+        assert_eq!((single.parts.len(), single.index.is_none()), (1, true));
+        assert_eq!(single.parts[0].title, "Short");
+    }
+
+    #[test]
+    fn incomplete_series() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let token = telegraph.create_account().short_name("Report").send().unwrap().access_token.unwrap();
+        let content: Vec<Node> = (0..20).map(|index| Node::p().text(&format!("{} {}", index, "text ".repeat(100))).into()).collect();
+
+        // "(10/10)" makes the title of the last part longer than 256 characters
+        let title = "T".repeat(249);
+        let error = telegraph.create_page_series(&token, &title, content)
+            .max_size(NAVIGATION_RESERVE + 1200)
+            .send()
+            .unwrap_err();
+        let incomplete = match error {
+            TelegraphError::IncompleteSeries(incomplete) => incomplete,
+            error => panic!("created parts must be returned: {:?}", error)
+        };
+        assert_eq!((incomplete.series.parts.len(), incomplete.count, incomplete.linked), (9, 10, 0));
+        assert!(matches!(incomplete.error, TelegraphError::ApiError(ApiErrorKind::TitleTooLong)));
+        assert_eq!(telegraph.get_page_list().access_token(&token).send().unwrap().total_count, 9);
+    }
+}
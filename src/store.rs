@@ -0,0 +1,398 @@
+//! Persistent store of account tokens
+//!
+//! Telegraph returns the access token of an account only from `createAccount`
+//! and `revokeAccessToken`. [`AccountStore`] keeps the tokens in a JSON file
+//! (or TOML with the `toml` feature, chosen by the `.toml` extension),
+//! readable only by the owner and rewritten atomically after every change.
+//!
+//! A store set with [`TelegraphBuilder::account_store`][crate::TelegraphBuilder::account_store]
+//! records every created account and every revoked token automatically.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::Account;
+use crate::TelegraphError;
+
+
+/// Account saved in an [`AccountStore`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredAccount {
+    /// Short name of the account
+    pub short_name: String,
+    /// Default author name of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_name: Option<String>,
+    /// Profile link of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_url: Option<String>,
+    /// Current access token
+    pub access_token: String,
+    /// Unix time of saving the account
+    pub created_at: u64,
+    /// Unix time of the last token revocation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotated_at: Option<u64>
+}
+
+
+#[derive(Default, Serialize, Deserialize)]
+struct StoreFile {
+    #[serde(default)]
+    accounts: Vec<StoredAccount>
+}
+
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    #[cfg(feature = "toml")]
+    Toml
+}
+
+
+struct Inner {
+    path: PathBuf,
+    format: Format,
+    accounts: Mutex<Vec<StoredAccount>>
+}
+
+
+/// Accounts saved in a file, looked up by short name
+///
+/// Clones share the same accounts, so the store can be used from several threads.
+///
+/// # Example
+/// ```rust, no_run
+/// use telegraph_api_rs::{AccountStore, Telegraph};
+///
+/// let store = AccountStore::open("accounts.json").unwrap();
+/// let telegraph = Telegraph::builder().account_store(store.clone()).build();
+/// telegraph.create_account().short_name("Blog").send().unwrap();
+///
+/// let token = store.get("Blog").unwrap().access_token;
+/// telegraph.revoke_access_token().access_token(&token).send().unwrap();
+/// assert_ne!(store.get("Blog").unwrap().access_token, token);
+/// ```
+#[derive(Clone)]
+pub struct AccountStore {
+    inner: Arc<Inner>
+}
+
+
+impl AccountStore {
+    /// Opens the store saved at `path`, empty if the file does not exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, TelegraphError> {
+        let path = path.as_ref().to_path_buf();
+        let format = format(&path)?;
+        let file = match fs::read_to_string(&path) {
+            Ok(text) => parse(&text, format)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => StoreFile::default(),
+            Err(error) => return Err(error.into())
+        };
+        let inner = Inner { path, format, accounts: Mutex::new(file.accounts) };
+        Ok(AccountStore { inner: Arc::new(inner) })
+    }
+
+    /// Path of the store file.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Account with the short name, the latest saved one if several accounts share it.
+    pub fn get(&self, short_name: &str) -> Option<StoredAccount> {
+        self.lock().iter().rev().find(|account| account.short_name == short_name).cloned()
+    }
+
+    /// All accounts in the order of saving.
+    pub fn accounts(&self) -> Vec<StoredAccount> {
+        self.lock().clone()
+    }
+
+    /// Saves an account returned by `createAccount`.
+    /// The account must have a short name and an access token.
+    pub fn insert(&self, account: &Account) -> Result<StoredAccount, TelegraphError> {
+        let (short_name, access_token) = match (&account.short_name, &account.access_token) {
+            (Some(short_name), Some(access_token)) => (short_name, access_token),
+            _ => return Err(invalid_input("account must have a short name and an access token"))
+        };
+        let account = StoredAccount {
+            short_name: short_name.clone(),
+            author_name: account.author_name.clone().filter(|name| !name.is_empty()),
+            author_url: account.author_url.clone().filter(|url| !url.is_empty()),
+            access_token: access_token.clone(),
+            created_at: now(),
+            rotated_at: None
+        };
+        let mut accounts = self.lock();
+        accounts.push(account.clone());
+        self.save(&accounts)?;
+        Ok(account)
+    }
+
+    /// Removes the account with the short name, the latest saved one if several accounts share it.
+    pub fn remove(&self, short_name: &str) -> Result<Option<StoredAccount>, TelegraphError> {
+        let mut accounts = self.lock();
+        let index = match accounts.iter().rposition(|account| account.short_name == short_name) {
+            Some(index) => index,
+            None => return Ok(None)
+        };
+        let account = accounts.remove(index);
+        self.save(&accounts)?;
+        Ok(Some(account))
+    }
+
+    /// Replaces `access_token` with the token of an account returned by `revokeAccessToken`.
+    /// Unknown tokens are ignored.
+    pub(crate) fn rotate(&self, access_token: &str, account: &Account) -> Result<(), TelegraphError> {
+        let new_token = match &account.access_token {
+            Some(new_token) => new_token,
+            None => return Ok(())
+        };
+        let mut accounts = self.lock();
+        let stored = match accounts.iter_mut().find(|account| account.access_token == access_token) {
+            Some(stored) => stored,
+            None => return Ok(())
+        };
+        stored.access_token = new_token.clone();
+        stored.rotated_at = Some(now());
+        self.save(&accounts)
+    }
+
+    /// Runs a write of the account on the blocking thread pool, so async requests
+    /// do not block the runtime, and wraps its error in [`TelegraphError::AccountNotSaved`].
+    #[cfg(feature = "async")]
+    pub(crate) async fn write_blocking<F>(&self, account: Account, write: F) -> Result<Account, TelegraphError>
+    where
+        F: FnOnce(&AccountStore, &Account) -> Result<(), TelegraphError> + Send + 'static
+    {
+        let store = self.clone();
+        let (account, result) = tokio::task::spawn_blocking(move || {
+            let result = write(&store, &account);
+            (account, result)
+        }).await.unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()));
+        match result {
+            Ok(()) => Ok(account),
+            Err(error) => Err(TelegraphError::AccountNotSaved(Box::new(account), Box::new(error)))
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<StoredAccount>> {
+        self.inner.accounts.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn save(&self, accounts: &[StoredAccount]) -> Result<(), TelegraphError> {
+        let file = StoreFile { accounts: accounts.to_vec() };
+        let text = match self.inner.format {
+            Format::Json => serde_json::to_string_pretty(&file)?,
+            #[cfg(feature = "toml")]
+            Format::Toml => toml::to_string_pretty(&file).map_err(invalid_data)?
+        };
+        Ok(write_private(&self.inner.path, text.as_bytes())?)
+    }
+}
+
+
+/// Writes a temporary file next to `path` and renames it over `path`,
+/// so readers see either the old or the new content.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    write_file(path, bytes, 0o666)
+}
+
+
+/// [`write_atomic`] readable only by the owner on Unix, for files with tokens.
+pub(crate) fn write_private(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    write_file(path, bytes, 0o600)
+}
+
+
+fn write_file(path: &Path, bytes: &[u8], mode: u32) -> io::Result<()> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    // Every writer gets its own temporary file, concurrent writes only race on the rename
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(path.file_name().unwrap_or_default());
+    tmp_name.push(format!(".{}.{}.tmp", std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed)));
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    let result = options.open(&tmp_path).and_then(|mut tmp_file| {
+        io::Write::write_all(&mut tmp_file, bytes)?;
+        tmp_file.sync_all()
+    });
+    match result.and_then(|()| fs::rename(&tmp_path, path)) {
+        Ok(()) => Ok(()),
+        Err(error) => {
+            let _ = fs::remove_file(&tmp_path);
+            Err(error)
+        }
+    }
+}
+
+
+fn format(path: &Path) -> Result<Format, TelegraphError> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        #[cfg(feature = "toml")]
+        Some("toml") => Ok(Format::Toml),
+        #[cfg(not(feature = "toml"))]
+        Some("toml") => Err(invalid_input("TOML store requires the `toml` feature")),
+        _ => Ok(Format::Json)
+    }
+}
+
+
+fn parse(text: &str, format: Format) -> Result<StoreFile, TelegraphError> {
+    match format {
+        Format::Json => Ok(serde_json::from_str(text)?),
+        #[cfg(feature = "toml")]
+        Format::Toml => toml::from_str(text).map_err(invalid_data)
+    }
+}
+
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+
+fn invalid_input(message: &str) -> TelegraphError {
+    io::Error::new(io::ErrorKind::InvalidInput, message).into()
+}
+
+
+#[cfg(feature = "toml")]
+fn invalid_data<E: std::error::Error + Send + Sync + 'static>(error: E) -> TelegraphError {
+    io::Error::new(io::ErrorKind::InvalidData, error).into()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::testing::FakeTelegraph;
+    #[cfg(feature = "toml")]
+    use crate::types::Account;
+    use crate::{Telegraph, TelegraphError};
+
+    use super::AccountStore;
+
+    #[test]
+    fn store_accounts() {
+        let dir = std::env::temp_dir().join(format!("telegraph-store-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("accounts.json");
+
+        let server = FakeTelegraph::start();
+        let store = AccountStore::open(&path).unwrap();
+        let telegraph = Telegraph::builder()
+            .api_base(server.url())
+            .account_store(store.clone())
+            .build();
+        let account = telegraph.create_account()
+            .short_name("Blog")
+            .author_name("Author")
+            .send()
+            .unwrap();
+        telegraph.create_account().short_name("Notes").send().unwrap();
+
+        let stored = store.get("Blog").unwrap();
+        assert_eq!(stored.access_token, account.access_token.unwrap());
+        assert_eq!(stored.author_name.as_deref(), Some("Author"));
+        assert_eq!(stored.rotated_at, None);
+
+        let revoked = telegraph.revoke_access_token().access_token(&stored.access_token).send().unwrap();
+        let rotated = store.get("Blog").unwrap();
+        assert_eq!(Some(&rotated.access_token), revoked.access_token.as_ref());
+        assert!(rotated.rotated_at.is_some());
+        assert!(telegraph.get_account_info().access_token(&rotated.access_token).send().is_ok());
+
+        let reopened = AccountStore::open(&path).unwrap();
+        assert_eq!(reopened.accounts(), store.accounts());
+        assert_eq!(reopened.remove("Notes").unwrap().unwrap().short_name, "Notes");
+        assert_eq!(reopened.remove("Notes").unwrap(), None);
+        assert_eq!(AccountStore::open(&path).unwrap().accounts().len(), 1);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unsaved_account() {
+        let dir = std::env::temp_dir().join(format!("telegraph-unsaved-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let server = FakeTelegraph::start();
+        let store = AccountStore::open(dir.join("accounts.json")).unwrap();
+        let telegraph = Telegraph::builder()
+            .api_base(server.url())
+            .account_store(store.clone())
+            .build();
+        let token = telegraph.create_account().short_name("Blog").send().unwrap().access_token.unwrap();
+
+        // Saving fails once the directory is gone, the new tokens are still returned
+        fs::remove_dir_all(&dir).unwrap();
+        let error = telegraph.revoke_access_token().access_token(&token).send().unwrap_err();
+        let revoked = error.unsaved_account().and_then(|account| account.access_token.clone()).unwrap();
+        assert!(telegraph.get_account_info().access_token(&revoked).send().is_ok());
+        let error = telegraph.create_account().short_name("Notes").send().unwrap_err();
+        assert!(matches!(&error, TelegraphError::AccountNotSaved(account, _) if account.access_token.is_some()));
+        assert!(error.to_string().starts_with("Account not saved: IO error"));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn async_store_accounts() {
+        let dir = std::env::temp_dir().join(format!("telegraph-async-store-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let server = FakeTelegraph::start();
+        let store = AccountStore::open(dir.join("accounts.json")).unwrap();
+        let telegraph = Telegraph::builder()
+            .api_base(server.url())
+            .account_store(store.clone())
+            .build_async();
+        let token = telegraph.create_account().short_name("Blog").send().await.unwrap().access_token.unwrap();
+        assert_eq!(store.get("Blog").unwrap().access_token, token);
+
+        let revoked = telegraph.revoke_access_token().access_token(&token).send().await.unwrap();
+        assert_eq!(Some(store.get("Blog").unwrap().access_token), revoked.access_token);
+
+        fs::remove_dir_all(&dir).unwrap();
+        let error = telegraph.create_account().short_name("Notes").send().await.unwrap_err();
+        assert!(matches!(&error, TelegraphError::AccountNotSaved(account, _) if account.access_token.is_some()));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_store() {
+        let path = std::env::temp_dir().join(format!("telegraph-store-{}.toml", std::process::id()));
+        let account = Account {
+            short_name: Some("Blog".into()),
+            access_token: Some("token".into()),
+            ..Account::default()
+        };
+        let stored = AccountStore::open(&path).unwrap().insert(&account).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("[[accounts]]"));
+        assert_eq!(AccountStore::open(&path).unwrap().get("Blog"), Some(stored));
+        fs::remove_file(&path).unwrap();
+    }
+}
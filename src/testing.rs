@@ -0,0 +1,726 @@
+//! Fake Telegraph server for offline testing
+//!
+//! [`FakeTelegraph`] starts an in-process HTTP server implementing every
+//! Telegraph API method and the upload endpoint with in-memory state.
+//! Errors are reported with the same strings as the real API
+//! (e.g. ACCESS_TOKEN_INVALID, PAGE_NOT_FOUND).
+//!
+//! # Example
+//! ```rust
+//! use telegraph_api_rs::testing::FakeTelegraph;
+//!
+//! let server = FakeTelegraph::start();
+//! let telegraph = server.telegraph();
+//! let account = telegraph.create_account()
+//! .short_name("Short name")
+//! .send()
+//! .unwrap();
+//! assert!(account.access_token.is_some());
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::content::{self, MAX_CONTENT_SIZE};
+use crate::types::Node;
+use crate::Telegraph;
+#[cfg(feature = "async")]
+use crate::AsyncTelegraph;
+
+
+/// Maximum number of pages returned by `getPageList`
+const MAX_PAGE_LIST_LIMIT: usize = 200;
+
+
+type ApiResult = Result<Value, &'static str>;
+
+
+struct AccountState {
+    short_name: String,
+    author_name: String,
+    author_url: String,
+    pages: Vec<String>
+}
+
+
+struct PageState {
+    account: usize,
+    title: String,
+    author_name: String,
+    author_url: String,
+    content: Value,
+    views: Vec<(i32, i32, i32, i32, u32)>
+}
+
+
+#[derive(Default)]
+struct State {
+    accounts: Vec<AccountState>,
+    tokens: HashMap<String, usize>,
+    pages: HashMap<String, PageState>,
+    uploads: usize,
+    failures: Vec<String>
+}
+
+
+/// In-process fake of the Telegraph API.
+///
+/// The server is stopped when `FakeTelegraph` is dropped.
+pub struct FakeTelegraph {
+    url: String,
+    server: Arc<Server>,
+    state: Arc<Mutex<State>>,
+    handle: Option<JoinHandle<()>>
+}
+
+
+impl FakeTelegraph {
+    /// Starts a new server on a random local port
+    pub fn start() -> Self {
+        let server = Arc::new(Server::http("127.0.0.1:0").expect("Failed to start fake server"));
+        let addr = server.server_addr().to_ip().expect("Fake server is not listening on ip");
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let handle = {
+            let server = server.clone();
+            let state = state.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle_request(&state, request);
+                }
+            })
+        };
+
+        FakeTelegraph {
+            url: format!("http://{}", addr),
+            server,
+            state,
+            handle: Some(handle)
+        }
+    }
+
+    /// Base url of the fake API
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Url of the fake upload endpoint
+    pub fn upload_url(&self) -> String {
+        format!("{}/upload", self.url)
+    }
+
+    /// [`Telegraph`] pointing at the fake server
+    pub fn telegraph(&self) -> Telegraph {
+        Telegraph::builder()
+            .api_base(&self.url)
+            .upload_url(&self.upload_url())
+            .build()
+    }
+
+    #[cfg(feature = "async")]
+    /// `AsyncTelegraph` pointing at the fake server
+    pub fn async_telegraph(&self) -> AsyncTelegraph {
+        Telegraph::builder()
+            .api_base(&self.url)
+            .upload_url(&self.upload_url())
+            .build_async()
+    }
+
+    /// Records `views` of the page at the given hour.
+    /// Returns `false` if the page does not exist.
+    pub fn add_views(&self, path: &str, year: i32, month: i32, day: i32, hour: i32, views: u32) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.pages.get_mut(path) {
+            Some(page) => {
+                page.views.push((year, month, day, hour, views));
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Number of files uploaded to the server
+    pub fn uploads(&self) -> usize {
+        self.state.lock().unwrap().uploads
+    }
+
+    /// Answers the next `count` API requests with `error`
+    /// (e.g. FLOOD_WAIT_5) without processing them.
+    pub fn fail_next(&self, error: &str, count: usize) {
+        self.state.lock().unwrap().failures = vec![error.to_string(); count];
+    }
+}
+
+
+impl Drop for FakeTelegraph {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+
+fn handle_request(state: &Mutex<State>, mut request: Request) {
+    let mut body = vec![];
+    if request.as_reader().read_to_end(&mut body).is_err() {
+        return;
+    }
+
+    let content_type = request.headers().iter()
+        .find(|header| header.field.equiv("Content-Type"))
+        .map(|header| header.value.as_str().to_string())
+        .unwrap_or_default();
+
+    let (method, query) = match request.url().split_once('?') {
+        Some((method, query)) => (method.to_string(), query.to_string()),
+        None => (request.url().to_string(), String::new())
+    };
+
+    let mut state = state.lock().unwrap();
+    let response = if method == "/upload" {
+        match upload(&mut state, &content_type, &body) {
+            Ok(result) => result,
+            Err(error) => json!({ "error": error })
+        }
+    } else if let Some(error) = state.failures.pop() {
+        json!({ "ok": false, "error": error })
+    } else {
+        let mut params: HashMap<String, String> = serde_urlencoded::from_str(&query).unwrap_or_default();
+        if content_type.starts_with("application/x-www-form-urlencoded") {
+            let form: HashMap<String, String> = serde_urlencoded::from_bytes(&body).unwrap_or_default();
+            params.extend(form);
+        }
+        let result = match method.trim_start_matches('/') {
+            "createAccount" => create_account(&mut state, &params),
+            "editAccountInfo" => edit_account_info(&mut state, &params),
+            "getAccountInfo" => get_account_info(&state, &params),
+            "revokeAccessToken" => revoke_access_token(&mut state, &params),
+            "createPage" => create_page(&mut state, &params),
+            "editPage" => edit_page(&mut state, &params),
+            "getPage" => get_page(&state, &params),
+            "getPageList" => get_page_list(&state, &params),
+            "getViews" => get_views(&state, &params),
+            _ => Err("METHOD_NOT_FOUND")
+        };
+        match result {
+            Ok(result) => json!({ "ok": true, "result": result }),
+            Err(error) => json!({ "ok": false, "error": error })
+        }
+    };
+    drop(state);
+
+    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let _ = request.respond(Response::from_string(response.to_string()).with_header(header));
+}
+
+
+fn random_hex(len: usize) -> String {
+    let mut out = String::new();
+    while out.len() < len {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos());
+        out.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    out.truncate(len);
+    out
+}
+
+
+/// Current (month, day) in UTC
+fn today() -> (u32, u32) {
+    let days = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / 86400;
+    // Civil from days, http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719468;
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (month as u32, day as u32)
+}
+
+
+fn param<'a>(params: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    params.get(name).map(String::as_str)
+}
+
+
+fn flag(params: &HashMap<String, String>, name: &str) -> bool {
+    matches!(param(params, name), Some("true") | Some("1"))
+}
+
+
+fn number(params: &HashMap<String, String>, name: &str) -> Result<Option<i32>, &'static str> {
+    match param(params, name) {
+        Some(value) if !value.is_empty() => value.parse().map(Some).map_err(|_| "PARAMS_INVALID"),
+        _ => Ok(None)
+    }
+}
+
+
+fn account_id(state: &State, params: &HashMap<String, String>) -> Result<usize, &'static str> {
+    param(params, "access_token")
+        .and_then(|token| state.tokens.get(token).copied())
+        .ok_or("ACCESS_TOKEN_INVALID")
+}
+
+
+fn account_json(account: &AccountState, fields: &[&str]) -> Value {
+    let mut result = serde_json::Map::new();
+    for field in fields {
+        let value = match *field {
+            "short_name" => json!(account.short_name),
+            "author_name" => json!(account.author_name),
+            "author_url" => json!(account.author_url),
+            "auth_url" => json!(format!("https://edit.telegra.ph/auth/{}", random_hex(42))),
+            "page_count" => json!(account.pages.len()),
+            _ => continue
+        };
+        result.insert(field.to_string(), value);
+    }
+    Value::Object(result)
+}
+
+
+fn create_account(state: &mut State, params: &HashMap<String, String>) -> ApiResult {
+    let short_name = match param(params, "short_name") {
+        Some(short_name) if !short_name.trim().is_empty() => short_name,
+        _ => return Err("SHORT_NAME_REQUIRED")
+    };
+    if short_name.chars().count() > 32 {
+        return Err("SHORT_NAME_TOO_LONG");
+    }
+    let account = AccountState {
+        short_name: short_name.into(),
+        author_name: param(params, "author_name").unwrap_or_default().into(),
+        author_url: param(params, "author_url").unwrap_or_default().into(),
+        pages: vec![]
+    };
+    let token = random_hex(60);
+    let mut result = account_json(&account, &["short_name", "author_name", "author_url", "auth_url"]);
+    result["access_token"] = json!(token);
+    state.accounts.push(account);
+    state.tokens.insert(token, state.accounts.len() - 1);
+    Ok(result)
+}
+
+
+fn edit_account_info(state: &mut State, params: &HashMap<String, String>) -> ApiResult {
+    let id = account_id(state, params)?;
+    let account = &mut state.accounts[id];
+    if let Some(short_name) = param(params, "short_name") {
+        if short_name.trim().is_empty() {
+            return Err("SHORT_NAME_REQUIRED");
+        }
+        account.short_name = short_name.into();
+    }
+    if let Some(author_name) = param(params, "author_name") {
+        account.author_name = author_name.into();
+    }
+    if let Some(author_url) = param(params, "author_url") {
+        account.author_url = author_url.into();
+    }
+    Ok(account_json(account, &["short_name", "author_name", "author_url"]))
+}
+
+
+fn get_account_info(state: &State, params: &HashMap<String, String>) -> ApiResult {
+    let id = account_id(state, params)?;
+    let fields: Vec<String> = match param(params, "fields") {
+        Some(fields) if fields != "null" => serde_json::from_str(fields).map_err(|_| "FIELDS_FORMAT_INVALID")?,
+        _ => vec!["short_name".into(), "author_name".into(), "author_url".into()]
+    };
+    let fields: Vec<&str> = fields.iter().map(String::as_str).collect();
+    Ok(account_json(&state.accounts[id], &fields))
+}
+
+
+fn revoke_access_token(state: &mut State, params: &HashMap<String, String>) -> ApiResult {
+    let id = account_id(state, params)?;
+    state.tokens.retain(|_, account| *account != id);
+    let token = random_hex(60);
+    state.tokens.insert(token.clone(), id);
+    let mut result = account_json(&state.accounts[id], &["auth_url"]);
+    result["access_token"] = json!(token);
+    Ok(result)
+}
+
+
+fn parse_content(params: &HashMap<String, String>) -> Result<Value, &'static str> {
+    let content = match param(params, "content") {
+        Some(content) if !content.is_empty() => content,
+        _ => return Err("CONTENT_REQUIRED")
+    };
+    if content.len() > MAX_CONTENT_SIZE {
+        return Err("CONTENT_TOO_BIG");
+    }
+    let value: Value = serde_json::from_str(content).map_err(|_| "CONTENT_FORMAT_INVALID")?;
+    serde_json::from_value::<Vec<Node>>(value.clone()).map_err(|_| "CONTENT_FORMAT_INVALID")?;
+    Ok(value)
+}
+
+
+fn parse_title(params: &HashMap<String, String>) -> Result<String, &'static str> {
+    match param(params, "title") {
+        Some(title) if !title.trim().is_empty() => {
+            if title.chars().count() > 256 {
+                Err("TITLE_TOO_LONG")
+            } else {
+                Ok(title.into())
+            }
+        },
+        _ => Err("TITLE_REQUIRED")
+    }
+}
+
+
+fn find_image(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(nodes) => nodes.iter().find_map(find_image),
+        Value::Object(element) => {
+            if element.get("tag") == Some(&json!("img")) {
+                if let Some(src) = element.get("attrs").and_then(|attrs| attrs.get("src")) {
+                    return src.as_str().map(String::from);
+                }
+            }
+            element.get("children").and_then(find_image)
+        },
+        _ => None
+    }
+}
+
+
+fn page_json(path: &str, page: &PageState, return_content: bool, can_edit: Option<bool>) -> Value {
+    let content: Vec<Node> = serde_json::from_value(page.content.clone()).unwrap_or_default();
+    let description = content::description(&content);
+
+    let mut result = json!({
+        "path": path,
+        "url": format!("https://telegra.ph/{}", path),
+        "title": page.title,
+        "description": description,
+        "views": page.views.iter().map(|views| views.4).sum::<u32>()
+    });
+    if !page.author_name.is_empty() {
+        result["author_name"] = json!(page.author_name);
+    }
+    if !page.author_url.is_empty() {
+        result["author_url"] = json!(page.author_url);
+    }
+    if let Some(image_url) = find_image(&page.content) {
+        result["image_url"] = json!(image_url);
+    }
+    if return_content {
+        result["content"] = page.content.clone();
+    }
+    if let Some(can_edit) = can_edit {
+        result["can_edit"] = json!(can_edit);
+    }
+    result
+}
+
+
+fn page_path(state: &State, title: &str) -> String {
+    let mut slug = String::new();
+    for ch in title.chars() {
+        if ch.is_alphanumeric() {
+            slug.push(ch);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+    let slug = if slug.is_empty() { "Untitled" } else { slug };
+    let (month, day) = today();
+    let base = format!("{}-{:02}-{:02}", slug, month, day);
+
+    let mut path = base.clone();
+    let mut index = 2;
+    while state.pages.contains_key(&path) {
+        path = format!("{}-{}", base, index);
+        index += 1;
+    }
+    path
+}
+
+
+fn create_page(state: &mut State, params: &HashMap<String, String>) -> ApiResult {
+    let id = account_id(state, params)?;
+    let title = parse_title(params)?;
+    let content = parse_content(params)?;
+    let account = &state.accounts[id];
+    let page = PageState {
+        account: id,
+        title,
+        author_name: param(params, "author_name").unwrap_or(&account.author_name).into(),
+        author_url: param(params, "author_url").unwrap_or(&account.author_url).into(),
+        content,
+        views: vec![]
+    };
+    let path = page_path(state, &page.title);
+    let result = page_json(&path, &page, flag(params, "return_content"), Some(true));
+    state.accounts[id].pages.push(path.clone());
+    state.pages.insert(path, page);
+    Ok(result)
+}
+
+
+fn edit_page(state: &mut State, params: &HashMap<String, String>) -> ApiResult {
+    let id = account_id(state, params)?;
+    let path = param(params, "path").ok_or("PAGE_NOT_FOUND")?;
+    let title = parse_title(params)?;
+    let content = parse_content(params)?;
+    let page = state.pages.get_mut(path).ok_or("PAGE_NOT_FOUND")?;
+    if page.account != id {
+        return Err("PAGE_ACCESS_DENIED");
+    }
+    page.title = title;
+    page.content = content;
+    if let Some(author_name) = param(params, "author_name") {
+        page.author_name = author_name.into();
+    }
+    if let Some(author_url) = param(params, "author_url") {
+        page.author_url = author_url.into();
+    }
+    Ok(page_json(path, page, flag(params, "return_content"), Some(true)))
+}
+
+
+fn get_page(state: &State, params: &HashMap<String, String>) -> ApiResult {
+    let path = param(params, "path").ok_or("PAGE_NOT_FOUND")?;
+    let page = state.pages.get(path).ok_or("PAGE_NOT_FOUND")?;
+    let can_edit = param(params, "access_token")
+        .map(|token| state.tokens.get(token) == Some(&page.account));
+    Ok(page_json(path, page, flag(params, "return_content"), can_edit))
+}
+
+
+fn get_page_list(state: &State, params: &HashMap<String, String>) -> ApiResult {
+    let id = account_id(state, params)?;
+    let offset = number(params, "offset")?.unwrap_or(0);
+    let limit = number(params, "limit")?.unwrap_or(50);
+    if offset < 0 {
+        return Err("OFFSET_INVALID");
+    }
+    if limit < 0 || limit as usize > MAX_PAGE_LIST_LIMIT {
+        return Err("LIMIT_INVALID");
+    }
+    let account = &state.accounts[id];
+    let pages: Vec<Value> = account.pages.iter().rev()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .map(|path| page_json(path, &state.pages[path], false, Some(true)))
+        .collect();
+    Ok(json!({ "total_count": account.pages.len(), "pages": pages }))
+}
+
+
+fn get_views(state: &State, params: &HashMap<String, String>) -> ApiResult {
+    let path = param(params, "path").ok_or("PAGE_NOT_FOUND")?;
+    let page = state.pages.get(path).ok_or("PAGE_NOT_FOUND")?;
+    let year = number(params, "year")?;
+    let month = number(params, "month")?;
+    let day = number(params, "day")?;
+    let hour = number(params, "hour")?;
+
+    if matches!(year, Some(year) if !(2000..=2100).contains(&year)) {
+        return Err("YEAR_INVALID");
+    }
+    if matches!(month, Some(month) if !(1..=12).contains(&month)) {
+        return Err("MONTH_INVALID");
+    }
+    if matches!(day, Some(day) if !(1..=31).contains(&day)) {
+        return Err("DAY_INVALID");
+    }
+    if matches!(hour, Some(hour) if !(0..=24).contains(&hour)) {
+        return Err("HOUR_INVALID");
+    }
+    if (month.is_some() && year.is_none()) || (day.is_some() && month.is_none()) || (hour.is_some() && day.is_none()) {
+        return Err("DATE_INVALID");
+    }
+
+    let views: u32 = page.views.iter()
+        .filter(|views| year.is_none_or(|year| views.0 == year))
+        .filter(|views| month.is_none_or(|month| views.1 == month))
+        .filter(|views| day.is_none_or(|day| views.2 == day))
+        .filter(|views| hour.is_none_or(|hour| views.3 == hour))
+        .map(|views| views.4)
+        .sum();
+    Ok(json!({ "views": views }))
+}
+
+
+fn upload(state: &mut State, content_type: &str, body: &[u8]) -> ApiResult {
+    let boundary = content_type.split(';')
+        .filter_map(|part| part.trim().strip_prefix("boundary="))
+        .next()
+        .ok_or("Bad request")?;
+    let delimiter = format!("--{}", boundary);
+    let body = String::from_utf8_lossy(body);
+
+    let mut result = vec![];
+    for part in body.split(delimiter.as_str()).skip(1) {
+        if part.starts_with("--") {
+            break;
+        }
+        let headers = part.split("\r\n\r\n").next().unwrap_or_default().to_ascii_lowercase();
+        let mime = headers.lines()
+            .filter_map(|line| line.strip_prefix("content-type:"))
+            .map(str::trim)
+            .next()
+            .unwrap_or_default();
+        let extension = match mime {
+            "image/jpeg" | "image/jpg" => "jpg",
+            "image/png" => "png",
+            "image/gif" => "gif",
+            "video/mp4" => "mp4",
+            _ => return Err("File type invalid")
+        };
+        result.push(json!({ "src": format!("/file/{}.{}", random_hex(20), extension) }));
+    }
+
+    if result.is_empty() {
+        return Err("File required");
+    }
+    state.uploads += result.len();
+    Ok(Value::Array(result))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+    use crate::types::AccountField;
+    use crate::error::ApiErrorKind;
+    use crate::TelegraphError;
+
+    use super::FakeTelegraph;
+
+    fn content() -> Vec<crate::types::Node> {
+        build_content(r#"[{"tag": "p", "children": ["Hello world"]}]"#).unwrap()
+    }
+
+    #[test]
+    fn account_methods() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let account = telegraph.create_account()
+            .short_name("Short name")
+            .author_name("Author name")
+            .send()
+            .unwrap();
+        assert_eq!(account.short_name.unwrap(), "Short name");
+        let token = account.access_token.unwrap();
+
+        let edited = telegraph.edit_account_info()
+            .access_token(&token)
+            .author_name("Author name 2")
+            .send()
+            .unwrap();
+        assert_eq!(edited.author_name.unwrap(), "Author name 2");
+
+        let info = telegraph.get_account_info()
+            .access_token(&token)
+            .fields(vec![AccountField::ShortName, AccountField::PageCount])
+            .send()
+            .unwrap();
+        assert_eq!(info.short_name.unwrap(), "Short name");
+        assert_eq!(info.page_count.unwrap(), 0);
+        assert!(info.author_name.is_none());
+
+        let revoked = telegraph.revoke_access_token()
+            .access_token(&token)
+            .send()
+            .unwrap();
+        assert_ne!(revoked.access_token.as_ref().unwrap(), &token);
+
+        let error = telegraph.get_account_info()
+            .access_token(&token)
+            .send()
+            .unwrap_err();
+        assert!(matches!(error, TelegraphError::ApiError(ApiErrorKind::AccessTokenInvalid)));
+    }
+
+    #[test]
+    fn page_methods() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let token = telegraph.create_account()
+            .short_name("Short name")
+            .send()
+            .unwrap()
+            .access_token
+            .unwrap();
+
+        let page = telegraph.create_page()
+            .access_token(&token)
+            .title("Hello world")
+            .content(content())
+            .return_content(true)
+            .send()
+            .unwrap();
+        assert!(page.path.starts_with("Hello-world-"));
+        assert_eq!(page.description, "Hello world");
+        assert_eq!(page.content.unwrap().len(), 1);
+
+        let edited = telegraph.edit_page()
+            .access_token(&token)
+            .path(&page.path)
+            .title("New title")
+            .content(content())
+            .send()
+            .unwrap();
+        assert_eq!(edited.title, "New title");
+        assert!(edited.content.is_none());
+
+        let fetched = telegraph.get_page().path(&page.path).send().unwrap();
+        assert_eq!(fetched.title, "New title");
+
+        let page_list = telegraph.get_page_list().access_token(&token).send().unwrap();
+        assert_eq!(page_list.total_count, 1);
+        assert_eq!(page_list.pages[0].path, page.path);
+
+        assert!(server.add_views(&page.path, 2022, 10, 15, 12, 5));
+        assert!(server.add_views(&page.path, 2022, 11, 1, 0, 2));
+        let views = telegraph.get_views().path(&page.path).send().unwrap();
+        assert_eq!(views.views, 7);
+        let views = telegraph.get_views().path(&page.path).year(2022).month(10).send().unwrap();
+        assert_eq!(views.views, 5);
+
+        let error = telegraph.get_page().path("Missing-01-01").send().unwrap_err();
+        assert!(matches!(error, TelegraphError::ApiError(ApiErrorKind::PageNotFound)));
+
+        let error = telegraph.create_page()
+            .access_token(&token)
+            .title("")
+            .content(content())
+            .send()
+            .unwrap_err();
+        assert!(matches!(error, TelegraphError::ApiError(ApiErrorKind::TitleRequired)));
+    }
+
+    #[cfg(feature = "upload")]
+    #[test]
+    fn upload() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let file = std::env::temp_dir().join(format!("telegraph-api-rs-fake-upload-{}.png", std::process::id()));
+        std::fs::write(&file, b"\x89PNG\r\n\x1a\n").unwrap();
+
+        let media = telegraph.upload(&[&file]).unwrap();
+        assert_eq!(media.len(), 1);
+        assert!(media[0].src.starts_with("/file/"));
+        assert_eq!(server.uploads(), 1);
+        std::fs::remove_file(file).unwrap();
+    }
+}
@@ -4,7 +4,7 @@ use super::TelegraphType;
 
 
 /// Available fields of the account struct
-#[derive(Serialize)]
+#[derive(Clone, Copy, Serialize)]
 pub enum AccountField {
     /// short_name
     #[serde(rename = "short_name")]
@@ -25,7 +25,7 @@ pub enum AccountField {
 
 
 /// Object represents a Telegraph account
-#[derive(Deserialize, Default, Debug)]
+#[derive(Deserialize, Serialize, Default, Debug)]
 pub struct Account {
     /// Account name, helps users with several accounts remember 
     /// which they are currently using. 
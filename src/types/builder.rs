@@ -0,0 +1,169 @@
+use super::{Node, NodeElement, NodeElementAttr, NodeElementAttrs, NodeTag};
+
+
+impl From<NodeElement> for Node {
+    fn from(element: NodeElement) -> Self {
+        Node::NodeElement(element)
+    }
+}
+
+
+impl From<String> for Node {
+    fn from(text: String) -> Self {
+        Node::String(text)
+    }
+}
+
+
+impl From<&str> for Node {
+    fn from(text: &str) -> Self {
+        Node::String(text.into())
+    }
+}
+
+
+macro_rules! element_constructors {
+    ($($name:ident => $tag:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("`", stringify!($name), "` element.")]
+            pub fn $name() -> NodeElement {
+                NodeElement::new(NodeTag::$tag)
+            }
+        )*
+    };
+}
+
+
+macro_rules! src_constructors {
+    ($($name:ident => $tag:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("`", stringify!($name), "` element with `src` attribute.")]
+            pub fn $name(src: &str) -> NodeElement {
+                NodeElement::new(NodeTag::$tag).attr(NodeElementAttr::Src(src.into()))
+            }
+        )*
+    };
+}
+
+
+/// Fluent constructors of content nodes
+///
+/// # Example
+/// ```rust
+/// use telegraph_api_rs::types::Node;
+///
+/// let content: Vec<Node> = vec![
+///     Node::h3().text("Title").into(),
+///     Node::p().text("Some ").child(Node::a("https://telegra.ph").text("link")).into(),
+/// ];
+/// ```
+impl Node {
+    /// Text node.
+    pub fn text(text: &str) -> Node {
+        Node::String(text.into())
+    }
+
+    /// `a` element with `href` attribute.
+    pub fn a(href: &str) -> NodeElement {
+        NodeElement::new(NodeTag::A).attr(NodeElementAttr::Href(href.into()))
+    }
+
+    element_constructors! {
+        aside => Aside, b => B, blockquote => Blockquote, br => Br,
+        code => Code, em => Em, figcaption => Figcaption, figure => Figure,
+        h3 => H3, h4 => H4, hr => Hr, i => I, li => Li, ol => Ol, p => P,
+        pre => Pre, s => S, strong => Strong, u => U, ul => Ul
+    }
+
+    src_constructors! {
+        iframe => Iframe, img => Img, video => Video
+    }
+}
+
+
+impl NodeElement {
+    /// Constructs an empty element with the `tag`.
+    pub fn new(tag: NodeTag) -> Self {
+        NodeElement { tag: Some(tag), attrs: None, children: None }
+    }
+
+    /// Sets the attribute of the element.
+    pub fn attr(mut self, attr: NodeElementAttr) -> Self {
+        self.attrs.get_or_insert_with(NodeElementAttrs::new).set(attr);
+        self
+    }
+
+    /// Sets the attribute of the element by its name.
+    pub fn attr_raw(mut self, name: &str, value: &str) -> Self {
+        self.attrs.get_or_insert_with(NodeElementAttrs::new).insert(name, value);
+        self
+    }
+
+    /// Appends a text node.
+    pub fn text(self, text: &str) -> Self {
+        self.child(text)
+    }
+
+    /// Appends a child node.
+    pub fn child<N: Into<Node>>(mut self, child: N) -> Self {
+        self.children.get_or_insert_with(Vec::new).push(child.into());
+        self
+    }
+
+    /// Appends child nodes.
+    pub fn children<I, N>(mut self, children: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+        N: Into<Node>
+    {
+        let mut children = children.into_iter().map(Into::into).peekable();
+        if children.peek().is_some() {
+            self.children.get_or_insert_with(Vec::new).extend(children);
+        }
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::build_content;
+
+    use super::Node;
+
+    #[test]
+    fn build_nodes() {
+        let content: Vec<Node> = vec![
+            Node::h3().text("Title").into(),
+            Node::p()
+                .child(Node::b().text("bold"))
+                .text(" text ")
+                .child(Node::a("https://telegra.ph").text("link"))
+                .into(),
+            Node::figure()
+                .child(Node::img("/file/1.jpg"))
+                .child(Node::figcaption().text("Caption"))
+                .into(),
+            Node::ul().children(["One", "Two"].map(|item| Node::li().text(item))).into(),
+            Node::hr().into()
+        ];
+        let expected = build_content(r#"[
+            {"tag": "h3", "children": ["Title"]},
+            {"tag": "p", "children": [
+                {"tag": "b", "children": ["bold"]},
+                " text ",
+                {"tag": "a", "attrs": {"href": "https://telegra.ph"}, "children": ["link"]}
+            ]},
+            {"tag": "figure", "children": [
+                {"tag": "img", "attrs": {"src": "/file/1.jpg"}},
+                {"tag": "figcaption", "children": ["Caption"]}
+            ]},
+            {"tag": "ul", "children": [
+                {"tag": "li", "children": ["One"]},
+                {"tag": "li", "children": ["Two"]}
+            ]},
+            {"tag": "hr"}
+        ]"#).unwrap();
+        assert_eq!(content, expected);
+    }
+}
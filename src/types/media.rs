@@ -1,4 +1,5 @@
-use serde::Deserialize;
+#[cfg(feature = "upload")]
+use serde::{Deserialize, Serialize};
 
 
 #[cfg(feature = "upload")]
@@ -13,7 +14,7 @@ pub enum UploadResult {
 
 #[cfg(feature = "upload")]
 /// Media file
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Media {
     /// Path of the file uploaded.
     pub src: String,
@@ -11,6 +11,7 @@ mod node_element;
 mod page_list;
 mod page_views;
 mod media;
+mod builder;
 
 
 use crate::TelegraphError;
@@ -18,7 +19,7 @@ use crate::TelegraphError;
 pub use self::account::{Account, AccountField};
 pub use self::page::Page;
 pub use self::node::Node;
-pub use self::node_element::{NodeElement, NodeTag, NodeElementAttr};
+pub use self::node_element::{NodeElement, NodeTag, NodeElementAttr, NodeElementAttrs};
 pub use self::page_list::PageList;
 pub use self::page_views::PageViews;
 #[cfg(feature = "upload")]
@@ -6,7 +6,7 @@ use super::{NodeElement, TelegraphType};
 #[allow(missing_docs)]
 /// This abstract object represents a DOM Node. 
 /// It can be a String which represents a DOM text node or a [`NodeElement`].
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum Node {
     String(String),
@@ -26,6 +26,7 @@ mod tests {
     use super::Node;
 
     #[test]
+    #[allow(clippy::iter_nth_zero)]
     fn node_text_deserialize() {
         let json = "[\"It's text node\"]";
         let nodes: Vec<Node> = serde_json::from_str(json).unwrap_or_default();
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::node::Node;
@@ -5,7 +7,7 @@ use super::node::Node;
 
 #[allow(missing_docs)]
 /// Available tags.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NodeTag {
     #[serde(rename = "a")]
     A,
@@ -58,9 +60,62 @@ pub enum NodeTag {
 }
 
 
+impl NodeTag {
+    /// All available tags.
+    pub const ALL: [NodeTag; 24] = [
+        NodeTag::A, NodeTag::Aside, NodeTag::B, NodeTag::Blockquote,
+        NodeTag::Br, NodeTag::Code, NodeTag::Em, NodeTag::Figcaption,
+        NodeTag::Figure, NodeTag::H3, NodeTag::H4, NodeTag::Hr,
+        NodeTag::I, NodeTag::Iframe, NodeTag::Img, NodeTag::Li,
+        NodeTag::Ol, NodeTag::P, NodeTag::Pre, NodeTag::S,
+        NodeTag::Strong, NodeTag::U, NodeTag::Ul, NodeTag::Video
+    ];
+
+    /// Name of the DOM element.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeTag::A => "a",
+            NodeTag::Aside => "aside",
+            NodeTag::B => "b",
+            NodeTag::Blockquote => "blockquote",
+            NodeTag::Br => "br",
+            NodeTag::Code => "code",
+            NodeTag::Em => "em",
+            NodeTag::Figcaption => "figcaption",
+            NodeTag::Figure => "figure",
+            NodeTag::H3 => "h3",
+            NodeTag::H4 => "h4",
+            NodeTag::Hr => "hr",
+            NodeTag::I => "i",
+            NodeTag::Iframe => "iframe",
+            NodeTag::Img => "img",
+            NodeTag::Li => "li",
+            NodeTag::Ol => "ol",
+            NodeTag::P => "p",
+            NodeTag::Pre => "pre",
+            NodeTag::S => "s",
+            NodeTag::Strong => "strong",
+            NodeTag::U => "u",
+            NodeTag::Ul => "ul",
+            NodeTag::Video => "video"
+        }
+    }
+
+    /// Tag by the name of the DOM element, `None` if Telegraph does not support it.
+    pub fn from_name(name: &str) -> Option<NodeTag> {
+        NodeTag::ALL.into_iter().find(|tag| tag.as_str() == name)
+    }
+
+    /// `true` if the element can not have children.
+    pub fn is_void(&self) -> bool {
+        matches!(self, NodeTag::Br | NodeTag::Hr | NodeTag::Img)
+    }
+}
+
+
 #[allow(missing_docs)]
 /// Available attrs.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub enum NodeElementAttr {
     #[serde(rename = "id")]
     ID(String),
@@ -71,16 +126,128 @@ pub enum NodeElementAttr {
 }
 
 
+impl NodeElementAttr {
+    /// Name of the attribute.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeElementAttr::ID(_) => "id",
+            NodeElementAttr::Href(_) => "href",
+            NodeElementAttr::Src(_) => "src"
+        }
+    }
+
+    /// Value of the attribute.
+    pub fn value(&self) -> &str {
+        match self {
+            NodeElementAttr::ID(value) | NodeElementAttr::Href(value) | NodeElementAttr::Src(value) => value
+        }
+    }
+}
+
+
+/// Attributes of the DOM element.
+///
+/// Keeps every attribute returned by the API, including unknown ones,
+/// with typed accessors for the [known][NodeElementAttr] attributes.
+#[derive(Deserialize, Serialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct NodeElementAttrs(BTreeMap<String, String>);
+
+
+impl NodeElementAttrs {
+    /// Constructs an empty attribute map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Value of the attribute with the `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Sets the attribute, returns the previous value.
+    pub fn insert(&mut self, name: &str, value: &str) -> Option<String> {
+        self.0.insert(name.into(), value.into())
+    }
+
+    /// Sets the known attribute, returns the previous value.
+    pub fn set(&mut self, attr: NodeElementAttr) -> Option<String> {
+        self.insert(attr.name(), attr.value())
+    }
+
+    /// Removes the attribute, returns its value.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.0.remove(name)
+    }
+
+    /// `id` attribute.
+    pub fn id(&self) -> Option<&str> {
+        self.get("id")
+    }
+
+    /// `href` attribute.
+    pub fn href(&self) -> Option<&str> {
+        self.get("href")
+    }
+
+    /// `src` attribute.
+    pub fn src(&self) -> Option<&str> {
+        self.get("src")
+    }
+
+    /// Known attributes of the element.
+    pub fn known(&self) -> impl Iterator<Item = NodeElementAttr> + '_ {
+        self.iter().filter_map(|(name, value)| match name {
+            "id" => Some(NodeElementAttr::ID(value.into())),
+            "href" => Some(NodeElementAttr::Href(value.into())),
+            "src" => Some(NodeElementAttr::Src(value.into())),
+            _ => None
+        })
+    }
+
+    /// Iterates over the attribute names and values, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Number of attributes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// `true` if there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+
+impl From<NodeElementAttr> for NodeElementAttrs {
+    fn from(attr: NodeElementAttr) -> Self {
+        let mut attrs = NodeElementAttrs::new();
+        attrs.set(attr);
+        attrs
+    }
+}
+
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for NodeElementAttrs {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        NodeElementAttrs(iter.into_iter().map(|(name, value)| (name.into(), value.into())).collect())
+    }
+}
+
+
 /// Object represents a DOM element node.
-#[derive(Deserialize, Serialize, Default, Debug)]
+#[derive(Deserialize, Serialize, Default, Debug, Clone, PartialEq, Eq)]
 pub struct NodeElement {
     /// Name of the DOM element. 
     /// Available tags [`NodeTag`]
     pub tag: Option<NodeTag>,
     /// Attributes of the DOM element. Key of object represents name of attribute, 
     /// value represents value of attribute. 
-    /// Available attributes [`NodeElementAttr`]
-    pub attrs: Option<NodeElementAttr>,
+    /// Known attributes [`NodeElementAttr`]
+    pub attrs: Option<NodeElementAttrs>,
     /// List of child nodes for the DOM element.
     pub children: Option<Vec<Node>>
 }
@@ -92,9 +259,10 @@ mod tests {
 
     use crate::types::NodeTag;
 
-    use super::{NodeElement, Node, NodeElementAttr};
+    use super::{NodeElement, Node, NodeElementAttr, NodeElementAttrs};
 
     #[test]
+    #[allow(clippy::iter_nth_zero)]
     fn node_elements_deserialize() {
         let node_el_str = r#"
         {
@@ -129,15 +297,26 @@ mod tests {
             "attrs": {"href": "link1"}
         }"#;
         let node_element: NodeElement = serde_json::from_str(node_el_str).unwrap_or_default();
-        let node_attr_element = if let Some(NodeElementAttr::Href(el)) = node_element.attrs {
-            el
-        } else {
-            "".into()
-        };
+        let node_attr_element = node_element.attrs.as_ref().and_then(NodeElementAttrs::href).unwrap_or_default();
 
         assert_eq!(node_attr_element, "link1"); 
     }
 
+    #[test]
+    fn node_elements_with_attrs_round_trip() {
+        let node_el_str = r#"{"tag":"img","attrs":{"data-id":"7","id":"pic","src":"/file/1.jpg"},"children":null}"#;
+        let node_element: NodeElement = serde_json::from_str(node_el_str).unwrap();
+        let attrs = node_element.attrs.as_ref().unwrap();
+
+        assert_eq!(attrs.src(), Some("/file/1.jpg"));
+        assert_eq!(attrs.id(), Some("pic"));
+        assert_eq!(attrs.get("data-id"), Some("7"));
+        assert_eq!(attrs.known().collect::<Vec<_>>(), vec![
+            NodeElementAttr::ID("pic".into()), NodeElementAttr::Src("/file/1.jpg".into())
+        ]);
+        assert_eq!(serde_json::to_string(&node_element).unwrap(), node_el_str);
+    }
+
     #[test]
     fn node_element_serialize() {
         let node_element = vec![NodeElement {
@@ -1,10 +1,10 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::{node::Node, TelegraphType};
 
 
 /// Object represents a page on Telegraph.
-#[derive(Deserialize, Default, Debug)]
+#[derive(Deserialize, Serialize, Default, Debug)]
 pub struct Page {
     /// Path to the page.
     pub path: String,
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::{page::Page, TelegraphType};
 
@@ -6,7 +6,7 @@ use super::{page::Page, TelegraphType};
 /// Object represents a list of Telegraph articles 
 /// belonging to an account. 
 /// Most recently created articles first.
-#[derive(Deserialize, Default, Debug)]
+#[derive(Deserialize, Serialize, Default, Debug)]
 pub struct PageList {
     /// Total number of pages belonging to the target Telegraph account.
     pub total_count: u32,
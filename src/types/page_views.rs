@@ -1,10 +1,10 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::TelegraphType;
 
 
 /// This object represents the number of page views for a Telegraph article.
-#[derive(Deserialize, Default, Debug)]
+#[derive(Deserialize, Serialize, Default, Debug)]
 pub struct PageViews {
     /// Number of page views for the target page.
     pub views: u32
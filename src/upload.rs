@@ -0,0 +1,344 @@
+//! Files for the upload endpoint
+//!
+//! [`UploadFile`] holds the content of a file in memory with its name and mime type,
+//! so files can be uploaded from paths, readers or bytes produced by the application.
+
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::content::rewrite_media;
+use crate::types::{Media, Node};
+use crate::TelegraphError;
+
+
+/// Maximum size of an uploaded file
+pub const MAX_UPLOAD_SIZE: usize = 5 * 1024 * 1024;
+
+
+/// File rejected before uploading
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadIssue {
+    /// Name of the offending file
+    pub file_name: String,
+    /// Kind of the problem
+    pub kind: UploadIssueKind
+}
+
+
+/// Kind of [`UploadIssue`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadIssueKind {
+    /// File is bigger than [`MAX_UPLOAD_SIZE`]
+    TooBig {
+        /// Size of the file in bytes
+        size: usize
+    },
+    /// Content is not a jpeg, png, gif or mp4 file
+    UnsupportedType,
+    /// Mime type set with [`UploadFile::with_mime`] does not match the content
+    MimeMismatch {
+        /// Mime type set for the file
+        mime: String,
+        /// Mime type detected from the content
+        detected: String
+    }
+}
+
+
+impl fmt::Display for UploadIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.file_name)?;
+        match &self.kind {
+            UploadIssueKind::TooBig { size } => write!(f, "file size {} exceeds {} bytes", size, MAX_UPLOAD_SIZE),
+            UploadIssueKind::UnsupportedType => write!(f, "unsupported file type, expected jpeg, png, gif or mp4"),
+            UploadIssueKind::MimeMismatch { mime, detected } => write!(f, "mime type {} does not match the content {}", mime, detected)
+        }
+    }
+}
+
+
+/// Mime type detected from the magic bytes of a supported file.
+pub fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if is_mp4(bytes) {
+        Some("video/mp4")
+    } else {
+        None
+    }
+}
+
+
+/// Brands of ISO base media files playable as mp4 video
+const MP4_BRANDS: [&[u8]; 13] = [
+    b"isom", b"iso2", b"iso3", b"iso4", b"iso5", b"iso6", b"mp41", b"mp42", b"avc1", b"dash", b"M4V ", b"M4VP", b"mmp4"
+];
+
+/// Brands of QuickTime, audio, image and 3GPP files, often compatible with an mp4 brand
+const OTHER_BRANDS: [&[u8]; 11] = [
+    b"qt  ", b"M4A ", b"M4B ", b"M4P ", b"heic", b"heix", b"hevc", b"mif1", b"msf1", b"avif", b"avis"
+];
+
+
+/// `true` for an `ftyp` box with an mp4 major brand, or with an unknown major brand
+/// and an mp4 compatible brand.
+fn is_mp4(bytes: &[u8]) -> bool {
+    if bytes.get(4..8) != Some(b"ftyp") {
+        return false;
+    }
+    let major = match bytes.get(8..12) {
+        Some(major) => major,
+        None => return false
+    };
+    if MP4_BRANDS.contains(&major) {
+        return true;
+    }
+    if major.starts_with(b"3g") || OTHER_BRANDS.contains(&major) {
+        return false;
+    }
+    let box_size = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+    let compatible = bytes.get(16..box_size.min(bytes.len())).unwrap_or_default();
+    compatible.chunks_exact(4).any(|brand| MP4_BRANDS.contains(&brand))
+}
+
+
+/// File to upload with [`Telegraph::upload_files`][crate::Telegraph::upload_files]
+///
+/// Files are checked before uploading: the content must be a jpeg, png, gif
+/// or mp4 file detected by its magic bytes and not bigger than [`MAX_UPLOAD_SIZE`],
+/// otherwise [`TelegraphError::InvalidUpload`] is returned.
+///
+/// # Example
+/// ```rust
+/// use telegraph_api_rs::upload::UploadFile;
+///
+/// let file = UploadFile::from_bytes(vec![0xff, 0xd8, 0xff], "photo");
+/// assert_eq!(file.mime(), "image/jpeg");
+///
+/// let file = UploadFile::from_reader(&b"GIF89a"[..], "animation.gif").unwrap().with_mime("image/gif");
+/// assert!(file.check().is_ok());
+///
+/// let file = UploadFile::from_bytes(b"BM".to_vec(), "image.bmp");
+/// assert!(file.check().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadFile {
+    bytes: Vec<u8>,
+    file_name: String,
+    mime: Option<String>
+}
+
+
+impl UploadFile {
+    /// File with the content in memory.
+    pub fn from_bytes<B: Into<Vec<u8>>>(bytes: B, file_name: &str) -> Self {
+        UploadFile { bytes: bytes.into(), file_name: file_name.into(), mime: None }
+    }
+
+    /// File with the content read to the end from `reader`.
+    ///
+    /// Reading stops after [`MAX_UPLOAD_SIZE`] bytes, a longer content is reported as
+    /// [`UploadIssueKind::TooBig`] with the size of the part read.
+    pub fn from_reader<R: Read>(reader: R, file_name: &str) -> Result<Self, TelegraphError> {
+        let mut bytes = vec![];
+        reader.take(MAX_UPLOAD_SIZE as u64 + 1).read_to_end(&mut bytes)?;
+        if bytes.len() > MAX_UPLOAD_SIZE {
+            return Err(too_big(file_name, bytes.len()));
+        }
+        Ok(Self::from_bytes(bytes, file_name))
+    }
+
+    /// File read from the `path`, named by the last component of the path.
+    ///
+    /// A file bigger than [`MAX_UPLOAD_SIZE`] is reported as [`UploadIssueKind::TooBig`] without reading it.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, TelegraphError> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let size = file.metadata()?.len();
+        if size > MAX_UPLOAD_SIZE as u64 {
+            return Err(too_big(&file_name(path), usize::try_from(size).unwrap_or(usize::MAX)));
+        }
+        Self::from_reader(file, &file_name(path))
+    }
+
+    /// Sets the mime type, it must match the mime type detected from the content.
+    pub fn with_mime(mut self, mime: &str) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+
+    /// Name of the file.
+    pub fn file_name(&self) -> &str {
+        &self.file_name
+    }
+
+    /// Content of the file.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Mime type of the file: the one set with [`with_mime`][UploadFile::with_mime],
+    /// detected from the content or guessed from the extension of the file name,
+    /// `application/octet-stream` if unknown.
+    pub fn mime(&self) -> String {
+        if let Some(mime) = &self.mime {
+            return mime.clone();
+        }
+        if let Some(mime) = sniff_mime(&self.bytes) {
+            return mime.into();
+        }
+        let mime = mime_guess::from_path(&self.file_name).first_or_octet_stream();
+        format!("{}/{}", mime.type_(), mime.subtype())
+    }
+
+    /// Checks the size and the type of the file, returns the detected mime type.
+    pub fn check(&self) -> Result<&'static str, UploadIssue> {
+        let issue = |kind| UploadIssue { file_name: self.file_name.clone(), kind };
+        if self.bytes.len() > MAX_UPLOAD_SIZE {
+            return Err(issue(UploadIssueKind::TooBig { size: self.bytes.len() }));
+        }
+        let detected = sniff_mime(&self.bytes).ok_or_else(|| issue(UploadIssueKind::UnsupportedType))?;
+        match &self.mime {
+            Some(mime) if mime != detected => Err(issue(UploadIssueKind::MimeMismatch {
+                mime: mime.clone(),
+                detected: detected.into()
+            })),
+            _ => Ok(detected)
+        }
+    }
+
+    pub(crate) fn into_part(self) -> Result<reqwest::blocking::multipart::Part, TelegraphError> {
+        let mime = self.check().map_err(TelegraphError::InvalidUpload)?;
+        Ok(reqwest::blocking::multipart::Part::bytes(self.bytes).file_name(self.file_name).mime_str(mime)?)
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn into_async_part(self) -> Result<reqwest::multipart::Part, TelegraphError> {
+        let mime = self.check().map_err(TelegraphError::InvalidUpload)?;
+        Ok(reqwest::multipart::Part::bytes(self.bytes).file_name(self.file_name).mime_str(mime)?)
+    }
+}
+
+
+/// Rewrites `sources` of the content to the uploaded `media` in the same order.
+pub(crate) fn apply_media(content: &mut [Node], sources: &[String], media: &[Media]) {
+    rewrite_media(content, |src| {
+        let index = sources.iter().position(|source| source == src)?;
+        media.get(index).map(|media| media.src.clone())
+    });
+}
+
+
+pub(crate) fn too_big(file_name: &str, size: usize) -> TelegraphError {
+    TelegraphError::InvalidUpload(UploadIssue { file_name: file_name.into(), kind: UploadIssueKind::TooBig { size } })
+}
+
+
+pub(crate) fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io;
+
+    use crate::content::local_media;
+    use crate::testing::FakeTelegraph;
+    use crate::types::Node;
+    use crate::{build_content, TelegraphError};
+
+    use super::{sniff_mime, UploadFile, UploadIssue, UploadIssueKind, MAX_UPLOAD_SIZE};
+
+    #[test]
+    fn check_files() {
+        assert_eq!(UploadFile::from_bytes(vec![], "clip.mp4").mime(), "video/mp4");
+        assert_eq!(UploadFile::from_bytes(vec![], "unknown").mime(), "application/octet-stream");
+        assert_eq!(UploadFile::from_bytes(b"\0\0\0\x18ftypmp42".to_vec(), "clip").check(), Ok("video/mp4"));
+        assert_eq!(sniff_mime(b"\0\0\0\x18ftypXAVC\0\0\0\0XAVCmp42"), Some("video/mp4"));
+        for other in [&b"ftypqt  \0\0\0\0qt  "[..], b"ftypheic\0\0\0\0mif1heic", b"ftypavif\0\0\0\0avifmif1",
+                b"ftyp3gp4\0\0\0\0isom3gp4", b"ftypM4A \0\0\0\0M4A mp42isom", b"ftypXAVC\0\0\0\0XAVC"] {
+            let mut bytes = vec![0, 0, 0, 4 + other.len() as u8];
+            bytes.extend_from_slice(other);
+            assert_eq!(sniff_mime(&bytes), None);
+        }
+
+        let issue = |file_name: &str, kind| Err(UploadIssue { file_name: file_name.into(), kind });
+        assert_eq!(UploadFile::from_bytes(b"BM".to_vec(), "image.jpg").check(), issue("image.jpg", UploadIssueKind::UnsupportedType));
+        let mut big = vec![0xff, 0xd8, 0xff];
+        big.resize(MAX_UPLOAD_SIZE + 1, 0);
+        assert_eq!(UploadFile::from_bytes(big, "big.jpg").check(), issue("big.jpg", UploadIssueKind::TooBig { size: MAX_UPLOAD_SIZE + 1 }));
+        assert_eq!(
+            UploadFile::from_bytes(b"GIF89a".to_vec(), "image").with_mime("image/png").check(),
+            issue("image", UploadIssueKind::MimeMismatch { mime: "image/png".into(), detected: "image/gif".into() })
+        );
+
+        let error = UploadFile::from_reader(io::repeat(0), "endless").unwrap_err();
+        assert!(matches!(error, TelegraphError::InvalidUpload(issue) if issue.kind == UploadIssueKind::TooBig { size: MAX_UPLOAD_SIZE + 1 }));
+        let path = std::env::temp_dir().join(format!("telegraph-big-{}.jpg", std::process::id()));
+        fs::File::create(&path).unwrap().set_len(MAX_UPLOAD_SIZE as u64 * 2).unwrap();
+        let error = UploadFile::from_path(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(error, TelegraphError::InvalidUpload(issue) if issue.kind == UploadIssueKind::TooBig { size: MAX_UPLOAD_SIZE * 2 }));
+    }
+
+    #[test]
+    fn upload_files() {
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let files = vec![
+            UploadFile::from_bytes(b"\x89PNG\r\n\x1a\n".to_vec(), "image.png"),
+            UploadFile::from_reader(&b"GIF89a"[..], "animation").unwrap().with_mime("image/gif")
+        ];
+        let media = telegraph.upload_files(files).unwrap();
+        assert_eq!(media.len(), 2);
+        assert_eq!(server.uploads(), 2);
+
+        let error = telegraph.upload_files(vec![UploadFile::from_bytes(b"text".to_vec(), "text.txt")]).unwrap_err();
+        assert!(matches!(error, TelegraphError::InvalidUpload(issue) if issue.file_name == "text.txt"));
+        assert_eq!(server.uploads(), 2);
+    }
+
+    #[test]
+    fn publish_local_media() {
+        let dir = std::env::temp_dir().join(format!("telegraph-media-{}", std::process::id()));
+        fs::create_dir_all(dir.join("figures")).unwrap();
+        fs::write(dir.join("figures/a.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+
+        let server = FakeTelegraph::start();
+        let telegraph = server.telegraph();
+        let token = telegraph.create_account().short_name("Media").send().unwrap().access_token.unwrap();
+        let content = build_content(r#"[
+            {"tag": "figure", "children": [{"tag": "img", "attrs": {"src": "figures/a.png"}}]},
+            {"tag": "img", "attrs": {"src": "figures/a.png"}},
+            {"tag": "img", "attrs": {"src": "https://example.com/b.png"}}
+        ]"#).unwrap();
+        let page = telegraph.create_page_with_media(content, &dir)
+            .unwrap()
+            .access_token(&token)
+            .title("Media")
+            .return_content(true)
+            .send()
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(server.uploads(), 1);
+        let content = page.content.unwrap();
+        assert!(local_media(&content).is_empty());
+        let (figure, image) = match (&content[0], &content[1]) {
+            (Node::NodeElement(figure), Node::NodeElement(image)) => (figure, image),
+            _ => panic!("content must keep its elements")
+        };
+        assert_eq!(figure.children.as_ref().unwrap()[0], content[1]);
+        assert!(image.attrs.as_ref().and_then(|attrs| attrs.src()).unwrap().starts_with("/file/"));
+    }
+}